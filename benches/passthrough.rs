@@ -0,0 +1,57 @@
+//! Benchmark of the passthrough fast path
+//!
+//! This benchmark compares escaping via escapers reporting passthrough
+//! characters against the same escapers wrapped in closures, which forces
+//! per-character processing.
+
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rescue_blanket::{Escapable, Escaper, TableEscaper, html, json};
+
+
+const ITERATIONS: u32 = 200;
+
+static HTML_TABLE: TableEscaper = TableEscaper::new(&[('&', "&amp;"), ('<', "&lt;"), ('>', "&gt;")]);
+
+
+fn main() {
+    let mostly_plain = "The quick brown fox jumps over the lazy dog. ".repeat(1000) + "<&>";
+    let mostly_special = "<&>\"'".repeat(10000);
+
+    for (name, input) in [("mostly plain", &mostly_plain), ("mostly special", &mostly_special)] {
+        println!("{name} ({} bytes):", input.len());
+        compare("html::Text", input, html::Text);
+        compare("html::Attribute", input, html::Attribute);
+        compare("json::StringContent", input, json::StringContent::new());
+        compare("TableEscaper", input, &HTML_TABLE);
+    }
+}
+
+
+/// Compare the run-based against the per-char path for the given escaper
+fn compare<E: Escaper>(name: &str, input: &str, escaper: E) {
+    let runs = measure(input, escaper.clone());
+    let mut per_char = escaper;
+    let per_char = measure(input, move |c| per_char.process(c));
+    println!(
+        "  {name:<20} runs: {:>10.2?}  per char: {:>10.2?}  speedup: {:.2}",
+        runs,
+        per_char,
+        per_char.as_secs_f64() / runs.as_secs_f64(),
+    );
+}
+
+
+/// Measure the average time for escaping the input
+fn measure(input: &str, escaper: impl Escaper) -> Duration {
+    let mut out = String::with_capacity(input.len() * 6);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        out.clear();
+        write!(out, "{}", black_box(input).escaped_with(escaper.clone())).unwrap();
+        black_box(&out);
+    }
+    start.elapsed() / ITERATIONS
+}
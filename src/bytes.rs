@@ -0,0 +1,230 @@
+//! Escaping of byte sequences
+//!
+//! Not all data which needs to be escaped is valid UTF-8: file names, header
+//! values or fields of binary protocols may contain arbitrary bytes. This
+//! module provides [ByteEscaper], the byte-wise counterpart of
+//! [Escaper](crate::Escaper), and [EscapedBytes], a wrapper implementing
+//! [Display] for escaping byte sequences while they are being formatted.
+//!
+//! [core::ascii::escape_default] may be used as a [ByteEscaper] directly. In
+//! addition, [Utf8] passes through valid UTF-8 while escaping invalid bytes.
+//!
+//! Values which are only available as `OsStr` may be escaped via their encoded
+//! bytes, i.e. via `OsStr::as_encoded_bytes`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::bytes::EscapableBytes;
+//! let data = b"Gr\xc3\xbc\xc3\x9fe\xc3\n";
+//! assert_eq!(data.escaped_ascii_default().to_string(), "Gr\\xc3\\xbc\\xc3\\x9fe\\xc3\\n");
+//! assert_eq!(data.escaped_utf8().to_string(), "Grüße\\xc3\\x0a");
+//! assert_eq!(b"\xe2\x98\xe2\x98\x83\xff".escaped_utf8().to_string(), "\\xe2\\x98☃\\xff");
+//!
+//! let name = std::ffi::OsStr::new("foo\\bar");
+//! assert_eq!(name.as_encoded_bytes().escaped_utf8().to_string(), "foo\\\\bar");
+//! ```
+
+use core::fmt::{self, Display};
+use core::str;
+
+
+/// Byte-wise processor implementing some escaping logic
+///
+/// This trait is the byte-wise counterpart of [Escaper](crate::Escaper). An
+/// impls' [process](ByteEscaper::process) function will receive one byte and
+/// produce an appropriate [Output](ByteEscaper::Output) implementing [Display].
+///
+/// # Note
+///
+/// A blanket implementation for `FnMut(u8) -> impl Display + Clone` is provided
+/// for users' convenience.
+pub trait ByteEscaper: Clone {
+    /// Partial output after escaping
+    ///
+    /// This type represents the output of processing a single input byte.
+    type Output: Display;
+
+    /// Process a single input byte
+    ///
+    /// This function processes a single input byte and produces as a result an
+    /// appropriate [Output](ByteEscaper::Output).
+    fn process(&mut self, input: u8) -> Self::Output;
+
+    /// Start processing a byte sequence
+    ///
+    /// This function is called once before the first byte of a sequence is
+    /// [process](ByteEscaper::process)ed. It may write some initial output to
+    /// `out`. The default implementation does nothing.
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
+
+    /// Finish processing a byte sequence
+    ///
+    /// This function is called once after the last byte of a sequence was
+    /// [process](ByteEscaper::process)ed. It may write some final output to
+    /// `out`. The default implementation does nothing.
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
+}
+
+impl<F: FnMut(u8) -> O + Clone, O: Display> ByteEscaper for F {
+    type Output = O;
+
+    fn process(&mut self, input: u8) -> Self::Output {
+        self(input)
+    }
+}
+
+
+/// Wrapper for escaping byte sequences during formatting
+///
+/// This type wraps a byte sequence together with a [ByteEscaper]. When
+/// displayed via its own implementation of [Display], the encapsulated bytes
+/// will be escaped via the [ByteEscaper] during the formatting process.
+///
+/// # Examples
+///
+/// ```
+/// let escaped = rescue_blanket::bytes::EscapedBytes::new(b"\0\xff", core::ascii::escape_default);
+/// assert_eq!(escaped.to_string(), "\\x00\\xff");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct EscapedBytes<B: AsRef<[u8]>, E: ByteEscaper> {
+    bytes: B,
+    escaper: E,
+}
+
+impl<B: AsRef<[u8]>, E: ByteEscaper> EscapedBytes<B, E> {
+    /// Create a new wrapper for the given bytes with a [ByteEscaper]
+    pub fn new(bytes: B, escaper: E) -> Self {
+        Self {bytes, escaper}
+    }
+
+    /// Create a new wrapper for the given bytes with a default [ByteEscaper]
+    pub fn new_default(bytes: B) -> Self where E: Default {
+        Self::new(bytes, Default::default())
+    }
+}
+
+impl<B: AsRef<[u8]>, E: ByteEscaper> Display for EscapedBytes<B, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut escaper = self.escaper.clone();
+        escaper.start(f)?;
+        self.bytes.as_ref().iter().try_for_each(|b| write!(f, "{}", escaper.process(*b)))?;
+        escaper.finish(f)
+    }
+}
+
+
+/// Convenience trait for escaping byte sequences
+///
+/// This trait augments types which can be viewed as byte slices with functions
+/// for wrapping them in instances of [EscapedBytes].
+pub trait EscapableBytes: AsRef<[u8]> + Sized {
+    /// Wrap this value in an [EscapedBytes] for escaped formatting
+    ///
+    /// The resulting [EscapedBytes] will escape the value when being formatted
+    /// via [Display] using the given [ByteEscaper].
+    fn escaped_bytes_with<E: ByteEscaper>(self, escaper: E) -> EscapedBytes<Self, E> {
+        EscapedBytes::new(self, escaper)
+    }
+
+    /// Wrap this value in an [EscapedBytes] for escaping with [core::ascii::escape_default]
+    ///
+    /// The resulting [EscapedBytes] will escape the value when being formatted
+    /// via [Display] using [core::ascii::escape_default] as [ByteEscaper].
+    fn escaped_ascii_default(self) -> EscapedBytes<Self, fn(u8) -> core::ascii::EscapeDefault> {
+        self.escaped_bytes_with(core::ascii::escape_default)
+    }
+
+    /// Wrap this value in an [EscapedBytes] for escaping with [Utf8]
+    ///
+    /// The resulting [EscapedBytes] will escape the value when being formatted
+    /// via [Display] using [Utf8] as [ByteEscaper].
+    fn escaped_utf8(self) -> EscapedBytes<Self, Utf8> {
+        self.escaped_bytes_with(Utf8::default())
+    }
+}
+
+impl<T: AsRef<[u8]>> EscapableBytes for T {}
+
+
+/// [ByteEscaper] passing through valid UTF-8
+///
+/// This escaper decodes the input as UTF-8. Valid characters are passed
+/// through, with the exception of ASCII control characters, which are escaped
+/// as `\xNN`, and `\`, which is escaped as `\\`. Bytes which are not part of a
+/// valid UTF-8 sequence are also escaped as `\xNN`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Utf8 {
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl ByteEscaper for Utf8 {
+    type Output = Output;
+
+    fn process(&mut self, input: u8) -> Self::Output {
+        let mut output = Output::default();
+
+        self.pending[self.pending_len] = input;
+        self.pending_len += 1;
+        match str::from_utf8(&self.pending[..self.pending_len]) {
+            Ok(s) => {
+                output.char = s.chars().next();
+                self.pending_len = 0;
+            },
+            Err(e) if e.error_len().is_none() => (),
+            Err(_) => {
+                // The pending bytes form a valid but incomplete prefix, which
+                // we need to escape. The current byte may still start a new
+                // sequence.
+                let len = core::mem::take(&mut self.pending_len) - 1;
+                if len == 0 {
+                    output.invalid[0] = input;
+                    output.invalid_len = 1;
+                } else {
+                    output.invalid[..len].copy_from_slice(&self.pending[..len]);
+                    let next = self.process(input);
+                    output.invalid[len..][..next.invalid_len].copy_from_slice(&next.invalid[..next.invalid_len]);
+                    output.invalid_len = len + next.invalid_len;
+                    output.char = next.char;
+                }
+            },
+        }
+        output
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let pending = &self.pending[..core::mem::take(&mut self.pending_len)];
+        pending.iter().try_for_each(|b| write!(out, "\\x{b:02x}"))
+    }
+}
+
+
+/// [Output](ByteEscaper::Output) of [Utf8]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    invalid: [u8; 4],
+    invalid_len: usize,
+    char: Option<char>,
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        self.invalid[..self.invalid_len].iter().try_for_each(|b| write!(f, "\\x{b:02x}"))?;
+        match self.char {
+            Some('\\') => f.write_str("\\\\"),
+            Some(c) if c.is_ascii_control() => write!(f, "\\x{:02x}", c as u8),
+            Some(c) => f.write_char(c),
+            None => Ok(()),
+        }
+    }
+}
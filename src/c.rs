@@ -0,0 +1,161 @@
+//! Escaper for C and C++ string literals
+//!
+//! This module provides [StringContent], an [Escaper] for the content of C and
+//! C++ string literals. Its output does not include the enclosing quotation
+//! marks. Characters other than printable ASCII are escaped byte-wise, based on
+//! their UTF-8 encoding, using either octal or hexadecimal escape sequences as
+//! selected via [Numeric].
+//!
+//! Hexadecimal escape sequences in C consume as many hex digits as follow them.
+//! Hence, if a hexadecimal escape sequence is followed by a hex digit, the
+//! literal is split into two adjacent literals, which the compiler will
+//! concatenate.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, c};
+//! let s = "\"Grüße\"\n??=";
+//! assert_eq!(
+//!     s.escaped_with(c::StringContent::new()).to_string(),
+//!     "\\\"Gr\\303\\274\\303\\237e\\\"\\n?\\?=",
+//! );
+//! assert_eq!(
+//!     s.escaped_with(c::StringContent::new().numeric(c::Numeric::Hex)).to_string(),
+//!     "\\\"Gr\\xc3\\xbc\\xc3\\x9f\" \"e\\\"\\n?\\?=",
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for C and C++ string literal content
+///
+/// This escaper escapes `"` and `\` as well as control characters with a short
+/// escape sequence (e.g. `\n`) using that sequence. The second `?` of any `??`
+/// is escaped as `\?` in order to avoid trigraphs. All other characters which
+/// are not printable ASCII are escaped byte-wise according to the [Numeric]
+/// escape style.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringContent {
+    numeric: Numeric,
+    state: State,
+}
+
+impl StringContent {
+    /// Create a new escaper using octal escape sequences
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the [Numeric] escape style
+    pub fn numeric(self, numeric: Numeric) -> Self {
+        Self {numeric, ..self}
+    }
+}
+
+impl Escaper for StringContent {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        let state = core::mem::take(&mut self.state);
+        match input {
+            '"'  => Output::Short("\\\""),
+            '\\' => Output::Short("\\\\"),
+            '\u{7}' => Output::Short("\\a"),
+            '\u{8}' => Output::Short("\\b"),
+            '\u{b}' => Output::Short("\\v"),
+            '\u{c}' => Output::Short("\\f"),
+            '\n' => Output::Short("\\n"),
+            '\r' => Output::Short("\\r"),
+            '\t' => Output::Short("\\t"),
+            '?' => {
+                self.state = State::Question;
+                if state == State::Question {
+                    Output::Short("\\?")
+                } else {
+                    Output::Char('?')
+                }
+            },
+            c if c.is_ascii_hexdigit() && state == State::Hex => Output::Split(c),
+            c if is_printable(c) => Output::Char(c),
+            c => {
+                let mut bytes = [0; 4];
+                let len = c.encode_utf8(&mut bytes).len();
+                match self.numeric {
+                    Numeric::Octal => Output::Octal {bytes, len},
+                    Numeric::Hex   => {
+                        self.state = State::Hex;
+                        Output::Hex {bytes, len}
+                    },
+                }
+            },
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.state == State::Plain && is_printable(input) && !matches!(input, '"' | '\\' | '?')
+    }
+}
+
+
+/// Style of numeric escape sequences
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Numeric {
+    /// Octal escape sequences with three digits, e.g. `\303`
+    #[default]
+    Octal,
+    /// Hexadecimal escape sequences with two digits, e.g. `\xc3`
+    Hex,
+}
+
+
+/// [Output](Escaper::Output) of [StringContent]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// The input character, preceded by `" "` for splitting the literal
+    Split(char),
+    /// A short escape sequence such as `\n`
+    Short(&'static str),
+    /// Octal escape sequences for the given UTF-8 encoded character
+    Octal {bytes: [u8; 4], len: usize},
+    /// Hexadecimal escape sequences for the given UTF-8 encoded character
+    Hex {bytes: [u8; 4], len: usize},
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)            => f.write_char(*c),
+            Self::Split(c)           => write!(f, "\" \"{c}"),
+            Self::Short(s)           => f.write_str(s),
+            Self::Octal {bytes, len} => bytes[..*len].iter().try_for_each(|b| write!(f, "\\{b:03o}")),
+            Self::Hex {bytes, len}   => bytes[..*len].iter().try_for_each(|b| write!(f, "\\x{b:02x}")),
+        }
+    }
+}
+
+
+/// State of a [StringContent]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum State {
+    /// The previous character requires no special treatment of the next one
+    #[default]
+    Plain,
+    /// The previous character was a `?`
+    Question,
+    /// The previous character was escaped via hexadecimal escape sequences
+    Hex,
+}
+
+
+/// Check whether a character is printable ASCII
+fn is_printable(c: char) -> bool {
+    matches!(c, ' '..='~')
+}
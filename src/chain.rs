@@ -0,0 +1,162 @@
+//! Composition of escapers
+//!
+//! Values sometimes need to be escaped for nested contexts, e.g. a JSON string
+//! embedded in an HTML attribute. This module provides [Chain], an [Escaper]
+//! feeding the output of an inner [Escaper] through an outer one.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Chain, Escapable, json, shell};
+//! let escaped = "it's \"quoted\"".escaped_with(Chain::new(json::StringContent::new(), shell::Quote));
+//! assert_eq!(escaped.to_string(), "'it'\\''s \\\"quoted\\\"'");
+//! ```
+
+use core::fmt::{self, Write};
+
+use crate::{Escaper, Feed, sink};
+
+
+/// [Escaper] composed of an inner and an outer [Escaper]
+///
+/// Each input `char` is processed by the inner [Escaper]. The output is then
+/// fed, `char` by `char`, through the outer [Escaper]. Both escapers carry
+/// their state across inputs.
+///
+/// Before the first character, the outer escaper is started, followed by the
+/// inner one, the output of which is also fed through the outer escaper. When
+/// finishing, the inner escaper is finished first.
+///
+/// Lookahead is supported for both escapers. The inner escaper performs its
+/// lookahead first. Afterwards, the outer escaper inspects the output of a
+/// separate instance of the inner escaper in an additional pass. Lookahead is
+/// performed anew for each value.
+///
+/// # Examples
+///
+/// ```
+/// use rescue_blanket::{Chain, Escapable, csv, json};
+/// let escaped = "a".escaped_with(csv::Field::new()).then(csv::Field::tsv());
+/// assert_eq!(escaped.to_string(), "a");
+/// let escaped = "a,b\t".escaped_with(csv::Field::new()).then(csv::Field::tsv());
+/// assert_eq!(escaped.to_string(), "\"\"\"a,b\t\"\"\"");
+///
+/// let chain = std::cell::RefCell::new(Chain::new(json::StringContent::new(), csv::Field::new()));
+/// assert_eq!("plain".escaped_with(&chain).to_string(), "plain");
+/// assert_eq!("a,b".escaped_with(&chain).to_string(), "\"a,b\"");
+/// assert_eq!("plain".escaped_with(&chain).to_string(), "plain");
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Chain<I: Escaper, O: Escaper> {
+    inner: I,
+    outer: O,
+    lookahead: Lookahead<I>,
+}
+
+impl<I: Escaper, O: Escaper> Chain<I, O> {
+    /// Create a new chain of an inner and an outer [Escaper]
+    pub fn new(inner: I, outer: O) -> Self {
+        Self {inner, outer, lookahead: Default::default()}
+    }
+}
+
+impl<I: Escaper, O: Escaper> Escaper for Chain<I, O> {
+    type Output = Output<I::Output, O>;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        let output = self.inner.process(input);
+        let outer = self.outer.clone();
+
+        // We need to advance the outer escaper's state as if the output was
+        // fed through it. The actual output will be produced by the clone.
+        let _ = write!(Feed {escaper: &mut self.outer, out: &mut sink::Discard}, "{}", output);
+        Output {output, outer}
+    }
+
+    fn process_into(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result {
+        let output = self.inner.process(input);
+        write!(Feed {escaper: &mut self.outer, out}, "{}", output)
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.inner.is_passthrough(input) && self.outer.is_passthrough(input)
+    }
+
+    fn start_lookahead(&mut self) -> bool {
+        match core::mem::replace(&mut self.lookahead, Lookahead::Done) {
+            Lookahead::Inner => if self.inner.start_lookahead() {
+                self.lookahead = Lookahead::Inner;
+                return true
+            },
+            Lookahead::Outer(mut shadow) => {
+                let _ = shadow.finish(&mut sink::Inspect::new(&mut self.outer));
+            },
+            Lookahead::Done => return false,
+        }
+
+        // The outer escaper inspects the output of the inner one, which we
+        // produce via a separate instance in order to keep the state intact.
+        if self.outer.start_lookahead() {
+            let mut shadow = self.inner.clone();
+            let _ = shadow.start(&mut sink::Inspect::new(&mut self.outer));
+            self.lookahead = Lookahead::Outer(shadow);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn inspect(&mut self, input: char) {
+        match &mut self.lookahead {
+            Lookahead::Inner         => self.inner.inspect(input),
+            Lookahead::Outer(shadow) => {
+                let _ = shadow.process_into(input, &mut sink::Inspect::new(&mut self.outer));
+            },
+            Lookahead::Done          => (),
+        }
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.outer.start(out)?;
+        self.inner.start(&mut Feed {escaper: &mut self.outer, out})
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.inner.finish(&mut Feed {escaper: &mut self.outer, out})?;
+        self.outer.finish(out)?;
+
+        // The next value requires its own lookahead
+        self.lookahead = Default::default();
+        Ok(())
+    }
+}
+
+
+/// [Output](Escaper::Output) of [Chain]
+///
+/// This type holds the output of the inner [Escaper] together with the state
+/// of the outer [Escaper] before processing it.
+#[derive(Copy, Clone, Debug)]
+pub struct Output<T: fmt::Display, O: Escaper> {
+    output: T,
+    outer: O,
+}
+
+impl<T: fmt::Display, O: Escaper> fmt::Display for Output<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(Feed {escaper: &mut self.outer.clone(), out: f}, "{}", self.output)
+    }
+}
+
+
+/// Lookahead state of a [Chain]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Lookahead<I> {
+    /// The inner escaper inspects the input
+    #[default]
+    Inner,
+    /// The outer escaper inspects the output of the given inner escaper
+    Outer(I),
+    /// Lookahead is complete
+    Done,
+}
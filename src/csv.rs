@@ -0,0 +1,191 @@
+//! Escaper for CSV and TSV fields
+//!
+//! This module provides [Field], an [Escaper] for individual fields of
+//! delimiter-separated values as specified in
+//! [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180). Fields containing the
+//! delimiter, the quote character or a line break are enclosed in quotes, with
+//! quote characters within doubled. All other fields are passed through as-is.
+//!
+//! Whether a field needs to be quoted depends on the field as a whole.
+//! [Field] therefore relies on [lookahead](Escaper#lookahead), which is
+//! performed by [Escaped](crate::Escaped). If no lookahead is performed, e.g.
+//! when writing via an [EscapingWriter](crate::EscapingWriter), fields are
+//! always quoted.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, csv};
+//! assert_eq!("plain".escaped_with(csv::Field::new()).to_string(), "plain");
+//! assert_eq!("a,b".escaped_with(csv::Field::new()).to_string(), "\"a,b\"");
+//! assert_eq!("say \"hi\"".escaped_with(csv::Field::new()).to_string(), "\"say \"\"hi\"\"\"");
+//! assert_eq!("a,b".escaped_with(csv::Field::tsv()).to_string(), "a,b");
+//! assert_eq!("a\tb".escaped_with(csv::Field::tsv()).to_string(), "\"a\tb\"");
+//! assert_eq!("a".escaped_with(csv::Field::new().always_quote(true)).to_string(), "\"a\"");
+//! assert_eq!(format!("[{:>7}]", "a,b".escaped_with(csv::Field::new())), "[  \"a,b\"]");
+//!
+//! let escaped = "a\"b".escaped_with(rescue_blanket::json::StringContent::new()).then(csv::Field::new());
+//! assert_eq!(escaped.to_string(), "\"a\\\"\"b\"");
+//!
+//! let mut writer = rescue_blanket::EscapingWriter::new(String::new(), csv::Field::new());
+//! std::fmt::Write::write_str(&mut writer, "plain").unwrap();
+//! assert_eq!(writer.finish().unwrap(), "\"plain\"");
+//! ```
+//!
+//! A [Field] shared between values decides on quoting for each value:
+//!
+//! ```
+//! use rescue_blanket::{Escapable, csv};
+//! use std::cell::RefCell;
+//!
+//! let field = RefCell::new(csv::Field::new());
+//! assert_eq!("plain".escaped_with(&field).to_string(), "plain");
+//! assert_eq!("a,b".escaped_with(&field).to_string(), "\"a,b\"");
+//! assert_eq!("plain".escaped_with(&field).to_string(), "plain");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for CSV and TSV fields
+///
+/// By default, this escaper uses `,` as delimiter and `"` as quote character.
+/// A field is quoted if it contains the delimiter, the quote character, `\n`
+/// or `\r`, or if quoting was not ruled out via
+/// [lookahead](Escaper#lookahead). Optionally, all fields may be quoted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    delimiter: char,
+    quote: char,
+    always_quote: bool,
+    quoting: Quoting,
+}
+
+impl Field {
+    /// Create a new escaper for comma-separated values
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a new escaper for tab-separated values
+    pub fn tsv() -> Self {
+        Self::new().delimiter('\t')
+    }
+
+    /// Set the delimiter separating fields
+    pub fn delimiter(self, delimiter: char) -> Self {
+        Self {delimiter, ..self}
+    }
+
+    /// Set the character for quoting fields
+    pub fn quote(self, quote: char) -> Self {
+        Self {quote, ..self}
+    }
+
+    /// Set whether to quote all fields, regardless of their content
+    pub fn always_quote(self, always_quote: bool) -> Self {
+        Self {always_quote, ..self}
+    }
+
+    /// Check whether a character requires the field to be quoted
+    fn is_special(&self, c: char) -> bool {
+        c == self.delimiter || c == self.quote || c == '\n' || c == '\r'
+    }
+
+    /// Check whether the field is quoted
+    fn is_quoted(&self) -> bool {
+        self.always_quote || self.quoting != Quoting::NotNeeded
+    }
+}
+
+impl Default for Field {
+    fn default() -> Self {
+        Self {delimiter: ',', quote: '"', always_quote: false, quoting: Default::default()}
+    }
+}
+
+impl Escaper for Field {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        if self.is_quoted() && input == self.quote {
+            Output::Quote(input)
+        } else {
+            Output::Char(input)
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !self.is_quoted() || input != self.quote
+    }
+
+    fn start_lookahead(&mut self) -> bool {
+        if self.always_quote || self.quoting != Quoting::Unknown {
+            return false
+        }
+        self.quoting = Quoting::NotNeeded;
+        true
+    }
+
+    fn inspect(&mut self, input: char) {
+        if self.is_special(input) {
+            self.quoting = Quoting::Needed;
+        }
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if self.is_quoted() {
+            out.write_char(self.quote)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if self.is_quoted() {
+            out.write_char(self.quote)?;
+        }
+
+        // The next value requires its own lookahead
+        self.quoting = Quoting::Unknown;
+        Ok(())
+    }
+}
+
+
+/// [Output](Escaper::Output) of [Field]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A quote character within a quoted field, which is doubled
+    Quote(char),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)  => f.write_char(*c),
+            Self::Quote(c) => {
+                f.write_char(*c)?;
+                f.write_char(*c)
+            },
+        }
+    }
+}
+
+
+/// Whether a [Field] needs to be quoted, as determined via lookahead
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum Quoting {
+    /// No lookahead was performed
+    #[default]
+    Unknown,
+    /// The field contains some special character
+    Needed,
+    /// The field contains no special characters
+    NotNeeded,
+}
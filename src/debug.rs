@@ -0,0 +1,116 @@
+//! Escaping of [Debug] output
+//!
+//! Many values worth logging or embedding implement only [Debug], but not
+//! [Display]. This module provides [EscapedDebug], a wrapper escaping the
+//! [Debug] representation of a value, and [EscapableDebug], the corresponding
+//! convenience trait.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{EscapableDebug, html};
+//! let v = vec!["<a>", "b"];
+//! assert_eq!((&v).debug_escaped_with(html::Text).to_string(), "[\"&lt;a&gt;\", \"b\"]");
+//! assert_eq!(
+//!     format!("{:#}", v.debug_escaped_with(char::escape_default)),
+//!     "[\\n    \\\"<a>\\\",\\n    \\\"b\\\",\\n]",
+//! );
+//! ```
+
+use core::fmt::{self, Debug, Display};
+
+use crate::{EscapingWriter, Escaper, look_ahead};
+
+
+/// Wrapper for escaping the [Debug] representation of items
+///
+/// This type wraps an item implementing [Debug] together with an [Escaper].
+/// When displayed via its own implementation of [Display], the [Debug]
+/// representation of the encapsulated item will be escaped via the [Escaper]
+/// during the formatting process.
+///
+/// If the alternate flag is set, e.g. via `{:#}`, the item's pretty-printed
+/// representation (`{:#?}`) is escaped. Other formatting flags are ignored.
+///
+/// Formatting via [Debug] yields the same escaped output as via [Display], so
+/// the item never ends up in the output unescaped.
+///
+/// # Examples
+///
+/// ```
+/// use rescue_blanket::{EscapableDebug, html};
+/// let escaped = "<a>".debug_escaped_with(html::Text);
+/// assert_eq!(format!("{escaped:?}"), "\"&lt;a&gt;\"");
+/// assert_eq!(format!("{escaped:#?}"), format!("{escaped:#}"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct EscapedDebug<I: Debug, E: Escaper> {
+    item: I,
+    escaper: E,
+}
+
+impl<I: Debug, E: Escaper> EscapedDebug<I, E> {
+    /// Create a new wrapper for the given item with an [Escaper]
+    pub fn new(item: I, escaper: E) -> Self {
+        Self {item, escaper}
+    }
+
+    /// Create a new wrapper for the given item with a default [Escaper]
+    pub fn new_default(item: I) -> Self where E: Default {
+        Self::new(item, Default::default())
+    }
+}
+
+impl<I: Debug, E: Escaper> Display for EscapedDebug<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        let pretty = f.alternate();
+        let escaper = if pretty {
+            look_ahead(&self.escaper, format_args!("{:#?}", self.item))?
+        } else {
+            look_ahead(&self.escaper, format_args!("{:?}", self.item))?
+        };
+        let mut out = EscapingWriter::new(f, escaper);
+        if pretty {
+            write!(out, "{:#?}", self.item)?;
+        } else {
+            write!(out, "{:?}", self.item)?;
+        }
+        out.finish().map(drop)
+    }
+}
+
+impl<I: Debug, E: Escaper> Debug for EscapedDebug<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+
+/// Convenience trait for escaping [Debug] representations of items
+///
+/// This trait augments types implementing [Debug] with functions for wrapping
+/// them in instances of [EscapedDebug], which will escape the value's [Debug]
+/// representation when being formatted.
+pub trait EscapableDebug: Debug + Sized {
+    /// Wrap this value in an [EscapedDebug] for escaped formatting
+    ///
+    /// The resulting [EscapedDebug] will escape the value's [Debug]
+    /// representation when being formatted via [Display] using the given
+    /// [Escaper].
+    fn debug_escaped_with<E: Escaper>(self, escaper: E) -> EscapedDebug<Self, E> {
+        EscapedDebug::new(self, escaper)
+    }
+
+    /// Wrap this value in an [EscapedDebug] for escaped formatting
+    ///
+    /// The resulting [EscapedDebug] will escape the value's [Debug]
+    /// representation when being formatted via [Display] using a default
+    /// [Escaper].
+    fn debug_escaped_with_default<E: Escaper + Default>(self) -> EscapedDebug<Self, E> {
+        EscapedDebug::new_default(self)
+    }
+}
+
+impl<T: Debug> EscapableDebug for T {}
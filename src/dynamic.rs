@@ -0,0 +1,289 @@
+//! Escapers selected at runtime or shared between values
+//!
+//! [Escaper] is not object safe, so escapers cannot be
+//! selected at runtime via trait objects directly. This module provides
+//! [DynEscaper], an object safe counterpart which is implemented for all
+//! [Escaper]s. In turn, `Box<dyn DynEscaper>` implements [Escaper] if the
+//! `alloc` feature is enabled.
+//!
+//! In addition, [Escaper] is implemented for references to
+//! [RefCell]s holding an escaper. Such a reference allows sharing a stateful
+//! escaper between multiple values. Note that clones of such a reference share
+//! the escaper's state. It is therefore unsuitable for uses relying on clones,
+//! e.g. computing the width of an [Escaped](crate::Escaped).
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use rescue_blanket::{Escapable, html, json};
+//! use rescue_blanket::dynamic::DynEscaper;
+//!
+//! fn escaper_for(format: &str) -> Box<dyn DynEscaper> {
+//!     match format {
+//!         "html" => Box::new(html::Text),
+//!         "json" => Box::new(json::StringContent::new()),
+//!         _ => Box::new(char::escape_default),
+//!     }
+//! }
+//!
+//! assert_eq!("<\"a\">".escaped_with(escaper_for("html")).to_string(), "&lt;\"a\"&gt;");
+//! assert_eq!("<\"a\">".escaped_with(escaper_for("json")).to_string(), "<\\\"a\\\">");
+//! # }
+//! ```
+//!
+//! Escapers which are [Send] and [Sync] may be boxed accordingly, e.g. for
+//! keeping them in shared configuration:
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use rescue_blanket::{Escapable, html};
+//! use rescue_blanket::dynamic::DynEscaper;
+//! use std::sync::Arc;
+//!
+//! struct Config {
+//!     escaper: Box<dyn DynEscaper + Send + Sync>,
+//! }
+//!
+//! let config = Arc::new(Config {escaper: Box::new(html::Text)});
+//! let shared = Arc::clone(&config);
+//! let handle = std::thread::spawn(move || "<a>".escaped_with(shared.escaper.clone()).to_string());
+//! assert_eq!(handle.join().unwrap(), "&lt;a&gt;");
+//! # }
+//! ```
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! use std::cell::RefCell;
+//!
+//! // An escaper numbering the lines across values
+//! let mut line = 0;
+//! let numbering = RefCell::new(move |c: char| {
+//!     if c == '\n' {
+//!         line += 1;
+//!         format!("\n{line}: ")
+//!     } else {
+//!         c.to_string()
+//!     }
+//! });
+//!
+//! let a = "a\nb".escaped_with(&numbering).to_string();
+//! let b = "c\nd".escaped_with(&numbering).to_string();
+//! assert_eq!(a + &b, "a\n1: bc\n2: d");
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::cell::RefCell;
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// Object safe counterpart of [Escaper]
+///
+/// This trait mirrors the functionality of [Escaper], but writes output to a
+/// `&mut dyn fmt::Write` rather than returning it. It is implemented for all
+/// [Escaper]s. If the `alloc` feature is enabled, `Box<dyn DynEscaper>`
+/// implements [Escaper] in turn, as do `Box<dyn DynEscaper + Send>` and
+/// `Box<dyn DynEscaper + Send + Sync>`.
+pub trait DynEscaper {
+    /// Process a single input character, writing the output to `out`
+    ///
+    /// See [Escaper::process_into].
+    fn process_dyn(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Check whether a character would be passed through unaltered
+    ///
+    /// See [Escaper::is_passthrough].
+    fn is_passthrough_dyn(&self, input: char) -> bool;
+
+    /// Prepare for inspecting the entire input ahead of processing
+    ///
+    /// See [Escaper::start_lookahead].
+    fn start_lookahead_dyn(&mut self) -> bool;
+
+    /// Inspect a single input character ahead of processing
+    ///
+    /// See [Escaper::inspect].
+    fn inspect_dyn(&mut self, input: char);
+
+    /// Start processing a string or value
+    ///
+    /// See [Escaper::start].
+    fn start_dyn(&mut self, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Finish processing a string or value
+    ///
+    /// See [Escaper::finish].
+    fn finish_dyn(&mut self, out: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Clone this escaper into a [Box]
+    #[cfg(feature = "alloc")]
+    fn clone_box<'a>(&self) -> Box<dyn DynEscaper + 'a> where Self: 'a;
+
+    /// Clone this escaper into a [Box] of a [Send] escaper
+    #[cfg(feature = "alloc")]
+    fn clone_box_send<'a>(&self) -> Box<dyn DynEscaper + Send + 'a> where Self: Send + 'a;
+
+    /// Clone this escaper into a [Box] of a [Send] and [Sync] escaper
+    #[cfg(feature = "alloc")]
+    fn clone_box_sync<'a>(&self) -> Box<dyn DynEscaper + Send + Sync + 'a> where Self: Send + Sync + 'a;
+}
+
+impl<E: Escaper> DynEscaper for E {
+    fn process_dyn(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.process_into(input, out)
+    }
+
+    fn is_passthrough_dyn(&self, input: char) -> bool {
+        self.is_passthrough(input)
+    }
+
+    fn start_lookahead_dyn(&mut self) -> bool {
+        self.start_lookahead()
+    }
+
+    fn inspect_dyn(&mut self, input: char) {
+        self.inspect(input)
+    }
+
+    fn start_dyn(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.start(out)
+    }
+
+    fn finish_dyn(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.finish(out)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn clone_box<'a>(&self) -> Box<dyn DynEscaper + 'a> where Self: 'a {
+        Box::new(self.clone())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn clone_box_send<'a>(&self) -> Box<dyn DynEscaper + Send + 'a> where Self: Send + 'a {
+        Box::new(self.clone())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn clone_box_sync<'a>(&self) -> Box<dyn DynEscaper + Send + Sync + 'a> where Self: Send + Sync + 'a {
+        Box::new(self.clone())
+    }
+}
+
+/// Implement [Clone] and [Escaper] for a boxed [DynEscaper] object type
+#[cfg(feature = "alloc")]
+macro_rules! impl_boxed {
+    ($lt:lifetime, $object:ty, $clone:ident) => {
+        impl<$lt> Clone for Box<$object> {
+            fn clone(&self) -> Self {
+                (**self).$clone()
+            }
+        }
+
+        impl<$lt> Escaper for Box<$object> {
+            type Output = Output<$lt>;
+
+            fn process(&mut self, input: char) -> Self::Output {
+                let escaper = (**self).clone_box();
+
+                // We need to advance our state as if the output was produced.
+                // The actual output will be produced by the clone.
+                let _ = (**self).process_dyn(input, &mut crate::sink::Discard);
+                Output {input, escaper}
+            }
+
+            fn process_into(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).process_dyn(input, out)
+            }
+
+            fn is_passthrough(&self, input: char) -> bool {
+                (**self).is_passthrough_dyn(input)
+            }
+
+            fn start_lookahead(&mut self) -> bool {
+                (**self).start_lookahead_dyn()
+            }
+
+            fn inspect(&mut self, input: char) {
+                (**self).inspect_dyn(input)
+            }
+
+            fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).start_dyn(out)
+            }
+
+            fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+                (**self).finish_dyn(out)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_boxed!('a, dyn DynEscaper + 'a, clone_box);
+#[cfg(feature = "alloc")]
+impl_boxed!('a, dyn DynEscaper + Send + 'a, clone_box_send);
+#[cfg(feature = "alloc")]
+impl_boxed!('a, dyn DynEscaper + Send + Sync + 'a, clone_box_sync);
+
+
+/// [Output](Escaper::Output) of `Box<dyn DynEscaper>`
+///
+/// This type holds an input `char` together with the state of the escaper
+/// before processing it.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct Output<'a> {
+    input: char,
+    escaper: Box<dyn DynEscaper + 'a>,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Output<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut escaper = (*self.escaper).clone_box();
+        (*escaper).process_dyn(self.input, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for Output<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Output").field("input", &self.input).finish_non_exhaustive()
+    }
+}
+
+
+impl<E: Escaper> Escaper for &RefCell<E> {
+    type Output = E::Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        self.borrow_mut().process(input)
+    }
+
+    fn process_into(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.borrow_mut().process_into(input, out)
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.borrow().is_passthrough(input)
+    }
+
+    fn start_lookahead(&mut self) -> bool {
+        self.borrow_mut().start_lookahead()
+    }
+
+    fn inspect(&mut self, input: char) {
+        self.borrow_mut().inspect(input)
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.borrow_mut().start(out)
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.borrow_mut().finish(out)
+    }
+}
@@ -0,0 +1,312 @@
+//! Escaping which may fail
+//!
+//! Some targets cannot represent certain characters at all, e.g. NUL in C
+//! strings or line breaks in HTTP header values. This module provides
+//! [TryEscaper], a variant of [Escaper](crate::Escaper) which may reject
+//! characters with a typed error, and [TryEscaped], the corresponding wrapper.
+//!
+//! As [fmt::Error] does not carry any information, [TryEscaped] retains the
+//! error reported by the [TryEscaper] if formatting fails. It may be retrieved
+//! via [TryEscaped::take_error]. Alternatively, [TryEscaped::try_write] reports
+//! the error directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Nul;
+//!
+//! let no_nul = |c| if c == '\0' { Err(Nul) } else { Ok(c) };
+//!
+//! let escaped = "foo\0bar".try_escaped_with(no_nul);
+//! assert!(std::fmt::write(&mut String::new(), format_args!("{}", escaped)).is_err());
+//! assert_eq!(escaped.take_error(), Some(Nul));
+//!
+//! let mut out = String::new();
+//! assert_eq!(escaped.try_write(&mut out), Err(rescue_blanket::fallible::Error::Escaper(Nul)));
+//! assert_eq!(out, "foo");
+//! ```
+//!
+//! Like an [Escaper](crate::Escaper), a [TryEscaper] may produce output before
+//! the first and after the last character:
+//!
+//! ```
+//! use rescue_blanket::{Escapable, TryEscaper, shell};
+//! use std::fmt;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Nul;
+//!
+//! // A single-quoted shell word, which cannot contain NUL
+//! #[derive(Clone)]
+//! struct ShellWord;
+//!
+//! impl TryEscaper for ShellWord {
+//!     type Output = shell::Output;
+//!     type Error = Nul;
+//!
+//!     fn try_process(&mut self, input: char) -> Result<Self::Output, Self::Error> {
+//!         match input {
+//!             '\0'  => Err(Nul),
+//!             '\'' => Ok(shell::Output::Quote),
+//!             c    => Ok(shell::Output::Char(c)),
+//!         }
+//!     }
+//!
+//!     fn is_passthrough(&self, input: char) -> bool {
+//!         !matches!(input, '\0' | '\'')
+//!     }
+//!
+//!     fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+//!         out.write_char('\'')
+//!     }
+//!
+//!     fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+//!         out.write_char('\'')
+//!     }
+//! }
+//!
+//! let mut out = String::new();
+//! assert_eq!("it's".try_escaped_with(ShellWord).try_write(&mut out), Ok(()));
+//! assert_eq!(out, "'it'\\''s'");
+//! assert!("a\0b".try_escaped_with(ShellWord).try_write(&mut String::new()).is_err());
+//! ```
+
+use core::cell::Cell;
+use core::fmt::{self, Display};
+
+
+/// Character-wise processor implementing some fallible escaping logic
+///
+/// This trait is the fallible counterpart of [Escaper](crate::Escaper). An
+/// impls' [try_process](TryEscaper::try_process) function will receive one
+/// character and produce either an appropriate [Output](TryEscaper::Output)
+/// or an [Error](TryEscaper::Error) if the character cannot be represented.
+///
+/// # Note
+///
+/// A blanket implementation for `FnMut(char) -> Result<impl Display, _> + Clone`
+/// is provided for users' convenience.
+///
+/// # Start, finish and passthrough
+///
+/// Like an [Escaper](crate::Escaper), a [TryEscaper] may implement
+/// [start](TryEscaper::start), [finish](TryEscaper::finish) and
+/// [is_passthrough](TryEscaper::is_passthrough). They carry the same meaning
+/// as their counterparts in [Escaper](crate::Escaper).
+pub trait TryEscaper: Clone {
+    /// Partial output after escaping
+    ///
+    /// This type represents the output of processing a single input `char`.
+    type Output: Display;
+
+    /// Error reported for characters which cannot be represented
+    type Error;
+
+    /// Process a single input character
+    ///
+    /// This function processes a single input `char` and produces as a result
+    /// an appropriate [Output](TryEscaper::Output) or an
+    /// [Error](TryEscaper::Error).
+    fn try_process(&mut self, input: char) -> Result<Self::Output, Self::Error>;
+
+    /// Check whether a character would be passed through unaltered
+    ///
+    /// See [Escaper::is_passthrough](crate::Escaper::is_passthrough). The
+    /// default implementation returns `false` for all characters.
+    fn is_passthrough(&self, input: char) -> bool {
+        let _ = input;
+        false
+    }
+
+    /// Start processing a string or value
+    ///
+    /// See [Escaper::start](crate::Escaper::start). The default implementation
+    /// does nothing.
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
+
+    /// Finish processing a string or value
+    ///
+    /// See [Escaper::finish](crate::Escaper::finish). The default
+    /// implementation does nothing.
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
+}
+
+impl<F: FnMut(char) -> Result<O, E> + Clone, O: Display, E> TryEscaper for F {
+    type Output = O;
+    type Error = E;
+
+    fn try_process(&mut self, input: char) -> Result<Self::Output, Self::Error> {
+        self(input)
+    }
+}
+
+
+/// Wrapper for fallibly escaping items during formatting
+///
+/// This type wraps an item implementing [Display] together with a
+/// [TryEscaper]. When displayed via its own implementation of [Display], the
+/// encapsulated item will be escaped via the [TryEscaper] during the formatting
+/// process. If the [TryEscaper] reports an error, formatting fails and the
+/// error is retained for retrieval via [take_error](TryEscaped::take_error).
+///
+/// As the error is retained in a [Cell], this type is not [Sync]. Users
+/// requiring a [Sync] wrapper may prefer reporting errors directly via
+/// [try_write](TryEscaped::try_write) or `try_to_string` on a wrapper created
+/// on demand.
+///
+/// Unlike [Escaped](crate::Escaped), this type does not honor the width and
+/// precision of the [fmt::Formatter].
+pub struct TryEscaped<I: Display, E: TryEscaper> {
+    item: I,
+    escaper: E,
+    error: Cell<Option<E::Error>>,
+}
+
+impl<I: Display, E: TryEscaper> TryEscaped<I, E> {
+    /// Create a new wrapper for the given item with a [TryEscaper]
+    pub fn new(item: I, escaper: E) -> Self {
+        Self {item, escaper, error: Cell::new(None)}
+    }
+
+    /// Create a new wrapper for the given item with a default [TryEscaper]
+    pub fn new_default(item: I) -> Self where E: Default {
+        Self::new(item, Default::default())
+    }
+
+    /// Retrieve the error reported during the last failed formatting
+    ///
+    /// This function returns the error reported by the [TryEscaper] during the
+    /// last formatting via [Display] which failed because of it, if any. The
+    /// error is removed from the wrapper.
+    pub fn take_error(&self) -> Option<E::Error> {
+        self.error.take()
+    }
+
+    /// Write the escaped item to the given [fmt::Write]
+    ///
+    /// Unlike formatting via [Display], this function reports errors from the
+    /// [TryEscaper] directly.
+    pub fn try_write(&self, out: &mut impl fmt::Write) -> Result<(), Error<E::Error>> {
+        use fmt::Write;
+
+        let mut error = None;
+        let mut proxy = WriteProxy {out, escaper: self.escaper.clone(), error: &mut error};
+        let res = proxy.escaper.start(&mut proxy.out)
+            .and_then(|_| write!(proxy, "{}", self.item))
+            .and_then(|_| proxy.escaper.finish(&mut proxy.out));
+        match (res, error) {
+            (_, Some(e))   => Err(Error::Escaper(e)),
+            (Err(e), None) => Err(Error::Format(e)),
+            (Ok(()), None) => Ok(()),
+        }
+    }
+
+    /// Format the escaped item into a `String`
+    ///
+    /// Unlike `ToString::to_string`, this function reports errors from the
+    /// [TryEscaper] rather than panicking.
+    #[cfg(feature = "alloc")]
+    pub fn try_to_string(&self) -> Result<alloc::string::String, Error<E::Error>> {
+        let mut res = alloc::string::String::new();
+        self.try_write(&mut res)?;
+        Ok(res)
+    }
+}
+
+impl<I: Display + Clone, E: TryEscaper> Clone for TryEscaped<I, E> {
+    fn clone(&self) -> Self {
+        Self::new(self.item.clone(), self.escaper.clone())
+    }
+}
+
+impl<I: Display + fmt::Debug, E: TryEscaper + fmt::Debug> fmt::Debug for TryEscaped<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryEscaped")
+            .field("item", &self.item)
+            .field("escaper", &self.escaper)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I: Display, E: TryEscaper> Display for TryEscaped<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.try_write(f).map_err(|e| match e {
+            Error::Escaper(e) => {
+                self.error.set(Some(e));
+                fmt::Error
+            },
+            Error::Format(e) => e,
+        })
+    }
+}
+
+
+/// Fallibly escaping [fmt::Write] implementation
+struct WriteProxy<'a, W: fmt::Write, E: TryEscaper> {
+    out: W,
+    escaper: E,
+    error: &'a mut Option<E::Error>,
+}
+
+impl<W: fmt::Write, E: TryEscaper> fmt::Write for WriteProxy<'_, W, E> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let error = &mut *self.error;
+        crate::write_runs(&mut self.escaper, &mut self.out, s, E::is_passthrough, |escaper, c, out| {
+            try_process_into(escaper, c, out, error)
+        })
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        try_process_into(&mut self.escaper, c, &mut self.out, self.error)
+    }
+}
+
+
+/// Process a single character, writing the output to `out`
+///
+/// If the [TryEscaper] reports an error, it is stored in `error`.
+fn try_process_into<E: TryEscaper>(
+    escaper: &mut E,
+    input: char,
+    out: &mut dyn fmt::Write,
+    error: &mut Option<E::Error>,
+) -> fmt::Result {
+    match escaper.try_process(input) {
+        Ok(output) => write!(out, "{}", output),
+        Err(e) => {
+            *error = Some(e);
+            Err(fmt::Error)
+        },
+    }
+}
+
+
+/// Error of fallible escaping
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error<E> {
+    /// The [TryEscaper] reported an error
+    Escaper(E),
+    /// Formatting failed for some other reason
+    Format(fmt::Error),
+}
+
+impl<E: Display> Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Escaper(e) => e.fmt(f),
+            Self::Format(e)  => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error> std::error::Error for Error<E> {}
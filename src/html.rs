@@ -0,0 +1,98 @@
+//! Escapers for HTML
+//!
+//! This module provides [Escaper]s for the two most common contexts in which
+//! values are embedded in HTML documents: [Text] content of elements and quoted
+//! [Attribute] values. Both are stateless and implement [Default], which makes
+//! them usable with [Escapable::escaped_with_default](crate::Escapable::escaped_with_default)
+//! and [Escaped::new_default](crate::Escaped::new_default).
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! let s = "<a href=\"x\">Tom & Jerry</a>";
+//! assert_eq!(
+//!     s.escaped_html().to_string(),
+//!     "&lt;a href=\"x\"&gt;Tom &amp; Jerry&lt;/a&gt;",
+//! );
+//! assert_eq!(
+//!     s.escaped_html_attr().to_string(),
+//!     "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&lt;/a&gt;",
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for HTML text content
+///
+/// This escaper replaces `&`, `<` and `>` with their respective character
+/// references. All other characters are passed through unaltered. The output is
+/// suitable for text content of elements, but not for attribute values.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Text;
+
+impl Escaper for Text {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '&' => Output::Reference("&amp;"),
+            '<' => Output::Reference("&lt;"),
+            '>' => Output::Reference("&gt;"),
+            c   => Output::Char(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !matches!(input, '&' | '<' | '>')
+    }
+}
+
+
+/// [Escaper] for quoted HTML attribute values
+///
+/// In addition to the characters escaped by [Text], this escaper also replaces
+/// `"` and `'` with character references. Hence, its output is suitable for
+/// attribute values quoted with either double or single quotes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attribute;
+
+impl Escaper for Attribute {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '"'  => Output::Reference("&quot;"),
+            '\'' => Output::Reference("&#39;"),
+            c    => Text.process(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !matches!(input, '"' | '\'') && Text.is_passthrough(input)
+    }
+}
+
+
+/// [Output](Escaper::Output) of the HTML [Escaper]s
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A character reference replacing the input character
+    Reference(&'static str),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)      => f.write_char(*c),
+            Self::Reference(r) => f.write_str(r),
+        }
+    }
+}
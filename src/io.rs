@@ -0,0 +1,179 @@
+//! Escaping for byte streams
+//!
+//! This module provides [EscapingWriter], an adapter escaping UTF-8 encoded
+//! data written to some [io::Write] via an [Escaper]. Unlike the
+//! [crate::EscapingWriter], it accepts arbitrary chunks of bytes rather than
+//! `str`s, allowing it to be used for writing directly to files or sockets.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::Write;
+//!
+//! let mut writer = rescue_blanket::io::EscapingWriter::new(Vec::new(), char::escape_default);
+//! let data = "\"Grüße\"".as_bytes();
+//! // Split the input in the middle of the encoding of `ü`
+//! writer.write_all(&data[..4]).unwrap();
+//! writer.write_all(&data[4..]).unwrap();
+//! assert_eq!(writer.finish().unwrap(), b"\\\"Gr\\u{fc}\\u{df}e\\\"");
+//! ```
+
+use std::fmt;
+use std::io;
+use std::str;
+
+use crate::Escaper;
+
+
+/// Escaping [io::Write] adapter
+///
+/// This type wraps an [io::Write] together with an [Escaper]. Bytes written to
+/// it are decoded as UTF-8, escaped via the [Escaper] and forwarded to the
+/// inner [io::Write], encoded as UTF-8. Encoded characters may be split across
+/// multiple calls to [write](io::Write::write). The same [Escaper] instance is
+/// used for all writes, i.e. state is carried across calls.
+///
+/// Invalid UTF-8 is reported as an [io::Error] of kind
+/// [InvalidData](io::ErrorKind::InvalidData).
+///
+/// [Escaper::start] is invoked before the first character is processed.
+/// [Escaper::finish] is invoked via [finish](EscapingWriter::finish), which
+/// should be called after the last write.
+#[derive(Debug)]
+pub struct EscapingWriter<W: io::Write, E: Escaper> {
+    inner: crate::EscapingWriter<Adapter<W>, E>,
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl<W: io::Write, E: Escaper> EscapingWriter<W, E> {
+    /// Create a new writer forwarding to `inner`, escaping via an [Escaper]
+    pub fn new(inner: W, escaper: E) -> Self {
+        let inner = crate::EscapingWriter::new(Adapter {inner, error: None}, escaper);
+        Self {inner, pending: [0; 4], pending_len: 0}
+    }
+
+    /// Retrieve a reference to the inner [io::Write]
+    pub fn get_ref(&self) -> &W {
+        &self.inner.get_ref().inner
+    }
+
+    /// Retrieve a mutable reference to the inner [io::Write]
+    ///
+    /// Writing to the inner [io::Write] directly bypasses escaping.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner.get_mut().inner
+    }
+
+    /// Finish escaping and retrieve the inner [io::Write]
+    ///
+    /// This function invokes [Escaper::finish], preceded by [Escaper::start] if
+    /// nothing was written yet. It fails if the data written ended with an
+    /// incomplete UTF-8 sequence.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.pending_len > 0 {
+            return Err(invalid_data())
+        }
+        self.inner.finish_in_place().map_err(|_| self.take_error())?;
+        Ok(self.into_inner())
+    }
+
+    /// Retrieve the inner [io::Write] without finishing escaping
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner().inner
+    }
+
+    /// Escape and forward a `str`
+    fn write_escaped(&mut self, s: &str) -> io::Result<()> {
+        use fmt::Write;
+
+        self.inner.write_str(s).map_err(|_| self.take_error())
+    }
+
+    /// Retrieve the [io::Error] underlying a [fmt::Error]
+    fn take_error(&mut self) -> io::Error {
+        self.inner.get_mut().error.take().unwrap_or_else(format_error)
+    }
+}
+
+impl<W: io::Write, E: Escaper> io::Write for EscapingWriter<W, E> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut consumed = 0;
+
+        // Complete any character split across calls
+        while self.pending_len > 0 {
+            let Some(byte) = buf.get(consumed) else { return Ok(consumed) };
+            self.pending[self.pending_len] = *byte;
+            self.pending_len += 1;
+            consumed += 1;
+
+            let pending = self.pending;
+            match str::from_utf8(&pending[..self.pending_len]) {
+                Ok(s) => {
+                    self.pending_len = 0;
+                    self.write_escaped(s)?;
+                },
+                Err(e) if e.error_len().is_none() => (),
+                Err(_) => {
+                    self.pending_len = 0;
+                    return Err(invalid_data())
+                },
+            }
+        }
+
+        let rest = &buf[consumed..];
+        match str::from_utf8(rest) {
+            Ok(s) => {
+                self.write_escaped(s)?;
+                Ok(buf.len())
+            },
+            Err(e) => {
+                let (valid, tail) = rest.split_at(e.valid_up_to());
+                self.write_escaped(str::from_utf8(valid).map_err(|_| invalid_data())?)?;
+                consumed += valid.len();
+
+                if e.error_len().is_none() {
+                    self.pending[..tail.len()].copy_from_slice(tail);
+                    self.pending_len = tail.len();
+                    Ok(buf.len())
+                } else if consumed > 0 {
+                    Ok(consumed)
+                } else {
+                    Err(invalid_data())
+                }
+            },
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+
+/// [fmt::Write] adapter for an [io::Write], retaining [io::Error]s
+#[derive(Debug)]
+struct Adapter<W: io::Write> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for Adapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+
+/// Create an [io::Error] indicating invalid UTF-8
+fn invalid_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+/// Create an [io::Error] indicating an error originating from escaping
+fn format_error() -> io::Error {
+    io::Error::other("escaper failed to format output")
+}
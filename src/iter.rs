@@ -0,0 +1,153 @@
+//! Escaping of `char` iterators
+//!
+//! Not all consumers of escaped output are part of the formatting machinery.
+//! This module provides [EscapeChars], an iterator adapter yielding the
+//! escaped `char`s of an underlying `Iterator<Item = char>`, analogous to the
+//! iterator returned by [str::escape_default].
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::iter::EscapableChars;
+//! let s = "foo=\"bar\"";
+//! let escaped: String = s.chars().escape_chars(char::escape_default).collect();
+//! assert_eq!(escaped, s.escape_default().to_string());
+//!
+//! let mut tokens = String::from("echo ");
+//! tokens.extend("it's".chars().escape_chars(rescue_blanket::shell::Quote));
+//! assert_eq!(tokens, "echo 'it'\\''s'");
+//! ```
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::{Escaper, sink};
+
+
+/// Iterator adapter yielding escaped `char`s
+///
+/// This iterator escapes the `char`s yielded by an inner iterator via an
+/// [Escaper], including the output of [Escaper::start] and [Escaper::finish].
+///
+/// No buffering is involved. Instead, the output for each input `char` is
+/// regenerated for each `char` yielded, which is cheap for the short outputs
+/// produced by typical [Escaper]s. If the [Escaper] fails to produce some
+/// output, iteration ends.
+#[derive(Clone, Debug)]
+pub struct EscapeChars<I: Iterator<Item = char>, E: Escaper> {
+    inner: I,
+    escaper: E,
+    state: State,
+}
+
+impl<I: Iterator<Item = char>, E: Escaper> EscapeChars<I, E> {
+    /// Create a new iterator escaping the `char`s of `inner` via an [Escaper]
+    pub fn new(inner: I, escaper: E) -> Self {
+        Self {inner, escaper, state: State::Initial}
+    }
+
+    /// Retrieve the `n`th `char` produced by a unit, without altering state
+    ///
+    /// Returns the `char` if the unit produces at least `n + 1` `char`s.
+    /// Otherwise, the escaper's state is advanced past the unit.
+    fn nth_of_unit(&mut self, unit: Unit, n: usize) -> Result<Option<char>, fmt::Error> {
+        let mut escaper = self.escaper.clone();
+        let mut out = sink::Nth::new(n);
+        let res = match unit {
+            Unit::Start       => escaper.start(&mut out),
+            Unit::Char(c)     => escaper.process_into(c, &mut out),
+            Unit::Passthrough => Ok(()),
+            Unit::Finish      => escaper.finish(&mut out),
+        };
+        match (out.found(), res) {
+            (Some(c), _) => Ok(Some(c)),
+            (None, Ok(())) => {
+                self.escaper = escaper;
+                Ok(None)
+            },
+            (None, Err(e)) => Err(e),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>, E: Escaper> Iterator for EscapeChars<I, E> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (unit, n) = match self.state {
+                State::Initial => (Unit::Start, 0),
+                State::Unit(unit, n) => (unit, n),
+                State::Done => return None,
+            };
+
+            match self.nth_of_unit(unit, n) {
+                Ok(Some(c)) => {
+                    self.state = State::Unit(unit, n + 1);
+                    return Some(c)
+                },
+                Ok(None) => (),
+                Err(_) => {
+                    self.state = State::Done;
+                    return None
+                },
+            }
+
+            if let Unit::Finish = unit {
+                self.state = State::Done;
+                return None
+            }
+
+            match self.inner.next() {
+                Some(c) if self.escaper.is_passthrough(c) => {
+                    self.state = State::Unit(Unit::Passthrough, 0);
+                    return Some(c)
+                },
+                Some(c) => self.state = State::Unit(Unit::Char(c), 0),
+                None => self.state = State::Unit(Unit::Finish, 0),
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>, E: Escaper> FusedIterator for EscapeChars<I, E> {}
+
+
+/// Convenience trait for escaping `char` iterators
+///
+/// This trait augments `char` iterators with a function for wrapping them in an
+/// [EscapeChars].
+pub trait EscapableChars: Iterator<Item = char> + Sized {
+    /// Escape the `char`s of this iterator via an [Escaper]
+    fn escape_chars<E: Escaper>(self, escaper: E) -> EscapeChars<Self, E> {
+        EscapeChars::new(self, escaper)
+    }
+}
+
+impl<T: Iterator<Item = char>> EscapableChars for T {}
+
+
+/// State of an [EscapeChars]
+#[derive(Copy, Clone, Debug)]
+enum State {
+    /// Nothing was yielded yet
+    Initial,
+    /// Yielding `char`s of a unit, with the index of the next one
+    Unit(Unit, usize),
+    /// Iteration ended
+    Done,
+}
+
+
+/// Unit of output of an [Escaper]
+#[derive(Copy, Clone, Debug)]
+enum Unit {
+    /// Output of [Escaper::start]
+    Start,
+    /// Output for a given input `char`
+    Char(char),
+    /// A `char` which was passed through and already yielded
+    Passthrough,
+    /// Output of [Escaper::finish]
+    Finish,
+}
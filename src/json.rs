@@ -0,0 +1,131 @@
+//! Escaper for JSON strings
+//!
+//! This module provides [StringContent], an [Escaper] for the content of JSON
+//! strings as specified in [RFC 8259](https://www.rfc-editor.org/rfc/rfc8259).
+//! Its output does not include the enclosing quotation marks.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, json};
+//! let s = "\"Grüße\"\n</script>";
+//! assert_eq!(
+//!     s.escaped_with(json::StringContent::new()).to_string(),
+//!     "\\\"Grüße\\\"\\n</script>",
+//! );
+//! assert_eq!(
+//!     s.escaped_with(json::StringContent::new().ascii_only(true).script_safe(true)).to_string(),
+//!     "\\\"Gr\\u00fc\\u00dfe\\\"\\n<\\/script>",
+//! );
+//! assert_eq!(
+//!     "\u{1F600}".escaped_with(json::StringContent::new().ascii_only(true)).to_string(),
+//!     "\\ud83d\\ude00",
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for JSON string content
+///
+/// By default, this escaper only escapes the characters RFC 8259 requires to be
+/// escaped: `"`, `\` and control characters. Control characters with a short
+/// escape sequence (e.g. `\n`) are escaped using that sequence, all others are
+/// escaped as `\u00XX`.
+///
+/// Optionally, all non-ASCII characters may be escaped, resulting in pure ASCII
+/// output. Characters outside the basic multilingual plane are then escaped as
+/// UTF-16 surrogate pairs.
+///
+/// Optionally, the output may also be made safe for embedding in HTML `<script>`
+/// elements. In this mode, the `/` in `</` and the characters U+2028 LINE
+/// SEPARATOR and U+2029 PARAGRAPH SEPARATOR are escaped.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringContent {
+    ascii_only: bool,
+    script_safe: bool,
+    after_lt: bool,
+}
+
+impl StringContent {
+    /// Create a new escaper escaping only what is strictly necessary
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set whether to escape all non-ASCII characters
+    pub fn ascii_only(self, ascii_only: bool) -> Self {
+        Self {ascii_only, ..self}
+    }
+
+    /// Set whether to make the output safe for embedding in `<script>` elements
+    pub fn script_safe(self, script_safe: bool) -> Self {
+        Self {script_safe, ..self}
+    }
+}
+
+impl Escaper for StringContent {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        let after_lt = core::mem::replace(&mut self.after_lt, self.script_safe && input == '<');
+        match input {
+            '"'  => Output::Short("\\\""),
+            '\\' => Output::Short("\\\\"),
+            '\u{8}' => Output::Short("\\b"),
+            '\u{c}' => Output::Short("\\f"),
+            '\n' => Output::Short("\\n"),
+            '\r' => Output::Short("\\r"),
+            '\t' => Output::Short("\\t"),
+            '/' if self.script_safe && after_lt => Output::Short("\\/"),
+            '\u{2028}' | '\u{2029}' if self.script_safe => Output::Unicode(input as u16),
+            c if c < '\u{20}' => Output::Unicode(c as u16),
+            c if self.ascii_only && !c.is_ascii() => {
+                let mut units = [0; 2];
+                match c.encode_utf16(&mut units) {
+                    [u] => Output::Unicode(*u),
+                    _   => Output::Pair(units[0], units[1]),
+                }
+            },
+            c => Output::Char(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        match input {
+            '"' | '\\' | '\u{0}'..='\u{1f}' => false,
+            '<' | '/' | '\u{2028}' | '\u{2029}' if self.script_safe => false,
+            _ if self.after_lt => false,
+            c => c.is_ascii() || !self.ascii_only,
+        }
+    }
+}
+
+
+/// [Output](Escaper::Output) of [StringContent]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A short escape sequence such as `\n`
+    Short(&'static str),
+    /// A `\uXXXX` escape sequence for the given UTF-16 code unit
+    Unicode(u16),
+    /// Two `\uXXXX` escape sequences for the given UTF-16 surrogate pair
+    Pair(u16, u16),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)      => f.write_char(*c),
+            Self::Short(s)     => f.write_str(s),
+            Self::Unicode(u)   => write!(f, "\\u{u:04x}"),
+            Self::Pair(hi, lo) => write!(f, "\\u{hi:04x}\\u{lo:04x}"),
+        }
+    }
+}
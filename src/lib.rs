@@ -21,6 +21,12 @@
 //! println!("foo=\"{}\"", "bar=\"baz\"".escaped_with(char::escape_default));
 //! ```
 //!
+//! For escaping output written to some [fmt::Write] piece by piece, the crate
+//! provides [EscapingWriter].
+//!
+//! The reverse operation is provided by [Unescaped], [Unescaper] and
+//! [Unescapable].
+//!
 //! # Why using `rescue_blanket`?
 //!
 //! There are a number of crates already for escaping strings, and there are
@@ -34,13 +40,45 @@
 //! escaping arises from the context the value is formatted in, not from the
 //! value itself.
 //!
-//! You could always format complex values into some buffer (e.g. [String]) and
+//! You could always format complex values into some buffer (e.g. `String`) and
 //! apply escaping on the result, but that requires the additional buffer and
 //! you may want to avoid that. Depending on the [Escaper], the use of [Escaped]
 //! does not involve any additional buffering.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::fmt::{self, Display};
 
+pub mod bytes;
+pub mod c;
+pub mod chain;
+pub mod csv;
+pub mod debug;
+pub mod dynamic;
+pub mod fallible;
+pub mod html;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iter;
+pub mod json;
+pub mod rust;
+pub mod shell;
+pub mod sql;
+pub mod table;
+pub mod truncate;
+pub mod unescape;
+pub mod url;
+pub mod xml;
+
+mod sink;
+
+pub use chain::Chain;
+pub use debug::{EscapableDebug, EscapedDebug};
+pub use fallible::{TryEscaped, TryEscaper};
+pub use table::TableEscaper;
+pub use unescape::{Unescapable, Unescaped, Unescaper};
+
 
 /// Character-wise processor implementing some escaping logic
 ///
@@ -66,6 +104,41 @@ use core::fmt::{self, Display};
 ///
 /// A blanket implementation for `FnMut(char) -> impl Display + Clone` is
 /// provided for users' convenience.
+///
+/// # Start and finish
+///
+/// Some escaping logic requires output before the first or after the last
+/// character, e.g. for enclosing a value in quotes or for flushing some state.
+/// For this purpose, an escaper may implement [start](Escaper::start) and
+/// [finish](Escaper::finish). Both are no-ops by default.
+///
+/// # Passthrough
+///
+/// Often, most characters do not need to be escaped. An escaper may report
+/// such characters via [is_passthrough](Escaper::is_passthrough), allowing
+/// runs of them to be forwarded without invoking [process](Escaper::process)
+/// for each individual character.
+///
+/// The output must not depend on whether passthrough is used, i.e. for each
+/// character reported, [process](Escaper::process) must yield that character
+/// unaltered without affecting the escaper's state.
+///
+/// # Lookahead
+///
+/// Some escaping logic depends on the input as a whole, e.g. whether a value
+/// needs to be quoted at all. An escaper may request to see the entire input
+/// ahead of processing by returning `true` from
+/// [start_lookahead](Escaper::start_lookahead). [Escaped] will then format the
+/// item an additional time, passing each character to
+/// [inspect](Escaper::inspect), and call
+/// [start_lookahead](Escaper::start_lookahead) again. Once it returns `false`,
+/// [start](Escaper::start) is invoked. Thus, an escaper may inspect the input
+/// multiple times, e.g. for composing escapers which perform lookahead.
+///
+/// Consumers which see their input only once, e.g. [EscapingWriter] or
+/// [EscapeChars](iter::EscapeChars), do not perform lookahead. Escapers
+/// relying on it need to produce correct output regardless, e.g. by assuming
+/// the worst case.
 pub trait Escaper: Clone {
     /// Partial output after escaping
     ///
@@ -76,9 +149,77 @@ pub trait Escaper: Clone {
     ///
     /// This function processes a single input `char` and produces as a result
     /// an appropriate [Output](Escaper::Output). The concatenation of the
-    /// results of [ToString::to_string] via [Display] for each
+    /// results of `ToString::to_string` via [Display] for each
     /// [Output](Escaper::Output) results in a correctly escaped `String`.
     fn process(&mut self, input: char) -> Self::Output;
+
+    /// Process a single input character, writing the output to `out`
+    ///
+    /// This function processes a single input `char` and writes the resulting
+    /// output to `out`. It is used by this library in place of
+    /// [process](Escaper::process) and needs to be equivalent to it. The
+    /// default implementation displays the [Output](Escaper::Output) of
+    /// [process](Escaper::process).
+    ///
+    /// Escapers for which constructing an [Output](Escaper::Output) is costly
+    /// may provide a more efficient implementation.
+    fn process_into(&mut self, input: char, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", self.process(input))
+    }
+
+    /// Check whether a character would be passed through unaltered
+    ///
+    /// If this function returns `true` for a given `char`, processing it in the
+    /// escaper's current state must produce an [Output](Escaper::Output)
+    /// displaying as that very `char` and must not alter the escaper's state.
+    /// [process](Escaper::process) may then be skipped for that `char`.
+    ///
+    /// The default implementation returns `false` for all characters.
+    fn is_passthrough(&self, input: char) -> bool {
+        let _ = input;
+        false
+    }
+
+    /// Prepare for inspecting the entire input ahead of processing
+    ///
+    /// This function is called by consumers supporting lookahead before the
+    /// input is passed to [inspect](Escaper::inspect). It returns whether the
+    /// escaper wishes to inspect the input (again). It is called repeatedly,
+    /// once after each pass over the input, until it returns `false`. The
+    /// default implementation returns `false`.
+    fn start_lookahead(&mut self) -> bool {
+        false
+    }
+
+    /// Inspect a single input character ahead of processing
+    ///
+    /// Each time [start_lookahead](Escaper::start_lookahead) returned `true`,
+    /// this function is called for each character of the input before
+    /// [start](Escaper::start) is called. The default implementation does
+    /// nothing.
+    fn inspect(&mut self, input: char) {
+        let _ = input;
+    }
+
+    /// Start processing a string or value
+    ///
+    /// This function is called once before the first character of a string or
+    /// value is [process](Escaper::process)ed. It may write some initial output
+    /// to `out`. The default implementation does nothing.
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
+
+    /// Finish processing a string or value
+    ///
+    /// This function is called once after the last character of a string or
+    /// value was [process](Escaper::process)ed. It may write some final output
+    /// to `out`. The default implementation does nothing.
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = out;
+        Ok(())
+    }
 }
 
 impl<F: FnMut(char) -> O + Clone, O: Display> Escaper for F {
@@ -102,27 +243,237 @@ impl<F: FnMut(char) -> O + Clone, O: Display> Escaper for F {
 /// over using this type directly. An exception may be the construction of
 /// interfaces enforcing some sort of escaping for inputs.
 ///
+/// # Width and precision
+///
+/// The [Display] implementation honors the [width](fmt::Formatter::width),
+/// [fill](fmt::Formatter::fill), [alignment](fmt::Formatter::align) and
+/// [precision](fmt::Formatter::precision) of the [fmt::Formatter]. The
+/// precision is interpreted as the maximum number of `char`s to display. By
+/// default, `char`s of the escaped output are counted. Alternatively, `char`s
+/// of the unescaped input may be counted (see [Count]).
+///
+/// When counting `char`s of the output, the output is cut only between complete
+/// [Output](Escaper::Output)s, as for [Truncated](truncate::Truncated). Thus,
+/// escape sequences are never cut in half. The output of [Escaper::finish] is
+/// included as long as it fits together with the output of [Escaper::start].
+/// Otherwise, nothing is displayed at all.
+///
+/// No additional buffering is involved. Instead, the item is formatted multiple
+/// times:
+///
+/// * If a width is specified, the item is formatted once for determining the
+///   length of the output.
+/// * If a precision is specified and `char`s of the output are counted, the
+///   item is formatted once for determining the length of the output. If the
+///   output needs to be cut, it is produced by processing each `char` on a
+///   clone of the [Escaper], followed by [Escaper::finish], before processing
+///   it for the actual output.
+/// * Each of the above, as well as the actual output, involves additional
+///   passes if the [Escaper] performs [lookahead](Escaper#lookahead).
+///
 /// # Examples
 ///
 /// ```
 /// let escaped = rescue_blanket::Escaped::new("foo=\"bar\"", char::escape_default);
 /// assert_eq!(escaped.to_string(), "foo=\\\"bar\\\"");
+/// assert_eq!(format!("[{:>14}]", escaped), "[   foo=\\\"bar\\\"]");
+/// assert_eq!(format!("[{:.5}]", escaped), "[foo=]");
+///
+/// let escaped = escaped.counting(rescue_blanket::Count::Input);
+/// assert_eq!(format!("[{:-^11}]", escaped), "[-foo=\\\"bar\\\"-]");
+/// assert_eq!(format!("[{:.5}]", escaped), "[foo=\\\"]");
+///
+/// let escaped = rescue_blanket::Escaped::new("abcdef", rescue_blanket::shell::Quote);
+/// assert_eq!(format!("[{:.5}]", escaped), "['abc']");
+/// assert_eq!(format!("[{:.1}]", escaped), "[]");
 /// ```
 #[derive(Copy, Clone, Debug)]
 pub struct Escaped<I: fmt::Display, E: Escaper> {
     item: I,
     escaper: E,
+    count: Count,
 }
 
 impl<I: fmt::Display, E: Escaper> Escaped<I, E> {
     /// Create a new wrapper for the given item with an [Escaper]
     pub fn new(item: I, escaper: E) -> Self {
-        Self {item, escaper}
+        Self {item, escaper, count: Default::default()}
     }
 
     /// Create a new wrapper for the given item with a default [Escaper]
     pub fn new_default(item: I) -> Self where E: Default {
-        Self {item, escaper: Default::default()}
+        Self::new(item, Default::default())
+    }
+
+    /// Set what to [Count] for width and precision
+    pub fn counting(self, count: Count) -> Self {
+        Self {count, ..self}
+    }
+
+    /// Escape the output of this wrapper's [Escaper] via another [Escaper]
+    ///
+    /// The resulting [Escaped] will escape the item via a [Chain] of the
+    /// current [Escaper] and `outer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::{Escapable, html, json};
+    /// let s = "\"a\" & b";
+    /// let escaped = s.escaped_with(json::StringContent::new()).then(html::Attribute);
+    /// assert_eq!(escaped.to_string(), "\\&quot;a\\&quot; &amp; b");
+    /// ```
+    pub fn then<O: Escaper>(self, outer: O) -> Escaped<I, Chain<E, O>> {
+        Escaped {item: self.item, escaper: Chain::new(self.escaper, outer), count: self.count}
+    }
+
+    /// Truncate the escaped output to the given [Budget](truncate::Budget)
+    ///
+    /// The resulting [Truncated](truncate::Truncated) will truncate the output
+    /// only between complete [Output](Escaper::Output)s.
+    pub fn truncated(self, budget: truncate::Budget) -> truncate::Truncated<I, E> {
+        truncate::Truncated::new(self, budget)
+    }
+
+    /// Check whether escaping alters the item
+    ///
+    /// This function returns `true` if formatting this wrapper would produce
+    /// output different from formatting the item on its own via [Display],
+    /// and `false` otherwise. The check does not involve any allocation and
+    /// stops at the first character which would be altered.
+    ///
+    /// Formatting flags such as width and precision are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Escapable;
+    /// assert!(!"foo bar".escaped_html().needs_escaping());
+    /// assert!("foo & bar".escaped_html().needs_escaping());
+    /// assert!("foo".escaped_shell().needs_escaping());
+    /// ```
+    pub fn needs_escaping(&self) -> bool {
+        use fmt::Write;
+
+        let mut escaper = match self.prepared_escaper() {
+            Ok(escaper) => escaper,
+            Err(_) => return true,
+        };
+
+        let mut expect = sink::Expect::new(None);
+        let res = escaper.start(&mut expect);
+        if !expect.matched(res) {
+            return true
+        }
+
+        if write!(sink::Compare::new(&mut escaper), "{}", self.item).is_err() {
+            return true
+        }
+
+        let mut expect = sink::Expect::new(None);
+        let res = escaper.finish(&mut expect);
+        !expect.matched(res)
+    }
+
+    /// Compute the [Length] of the escaped item
+    ///
+    /// This function formats the escaped item into a counting sink, without
+    /// any allocation. It fails if formatting fails.
+    ///
+    /// Formatting flags such as width and precision are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::{Escapable, Length};
+    /// assert_eq!("a < ä".escaped_html().escaped_len(), Ok(Length {bytes: 9, chars: 8}));
+    /// ```
+    pub fn escaped_len(&self) -> Result<Length, fmt::Error> {
+        use fmt::Write;
+
+        let mut counter = sink::Counter::default();
+        let mut writer = EscapingWriter::new(&mut counter, self.prepared_escaper()?);
+        write!(writer, "{}", self.item)?;
+        writer.finish()?;
+        Ok(Length {bytes: counter.bytes, chars: counter.chars})
+    }
+
+    /// Retrieve a fresh [Escaper] which performed lookahead on the item
+    pub(crate) fn prepared_escaper(&self) -> Result<E, fmt::Error> {
+        look_ahead(&self.escaper, format_args!("{}", self.item))
+    }
+
+    /// Write the escaped item, without any limit
+    pub(crate) fn write_escaped(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        use fmt::Write;
+
+        let mut writer = EscapingWriter::new(out, self.prepared_escaper()?);
+        write!(writer, "{}", self.item)?;
+        writer.finish_in_place()
+    }
+
+    /// Write the escaped item, limited to `limit` [Count]ed `char`s
+    fn write_limited(&self, out: &mut dyn fmt::Write, limit: usize) -> fmt::Result {
+        use fmt::Write;
+
+        if limit == usize::MAX {
+            return self.write_escaped(out)
+        }
+
+        match self.count {
+            Count::Output => truncate::write_truncated(self, out, truncate::Budget::Chars(limit), ""),
+            Count::Input => {
+                let mut writer = EscapingWriter::new(out, self.prepared_escaper()?);
+                let mut input = sink::Limited::new(&mut writer, limit);
+                let res = write!(input, "{}", self.item);
+                input.result(res)?;
+                writer.finish().map(drop)
+            },
+        }
+    }
+
+    /// Determine the number of [Count]ed `char`s, limited to `limit`
+    fn count_limited(&self, limit: usize) -> Result<usize, fmt::Error> {
+        use fmt::Write;
+
+        let mut counter = sink::Counter::default();
+        match self.count {
+            Count::Output => self.write_limited(&mut counter, limit)?,
+            Count::Input => {
+                let mut input = sink::Limited::new(&mut counter, limit);
+                let res = write!(input, "{}", self.item);
+                input.result(res)?
+            },
+        }
+        Ok(counter.chars)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, E: Escaper> Escaped<&'a str, E> {
+    /// Retrieve the escaped `str`, borrowing it if it needs no escaping
+    ///
+    /// This function returns the wrapped `str` itself if it does not [need
+    /// escaping](Escaped::needs_escaping). Otherwise, the escaped `str` is
+    /// returned as an owned `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Escapable;
+    /// use std::borrow::Cow;
+    ///
+    /// assert!(matches!("foo bar".escaped_html().to_cow(), Cow::Borrowed("foo bar")));
+    /// assert_eq!("foo & bar".escaped_html().to_cow(), "foo &amp; bar");
+    /// ```
+    pub fn to_cow(&self) -> alloc::borrow::Cow<'a, str> {
+        use alloc::string::ToString;
+
+        if self.needs_escaping() {
+            self.to_string().into()
+        } else {
+            self.item.into()
+        }
     }
 }
 
@@ -134,35 +485,205 @@ impl<I: fmt::Display, E: Escaper + Default> From<I> for Escaped<I, E> {
 
 impl<I: fmt::Display, E: Escaper> Display for Escaped<I, E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use fmt::Write;
+        use fmt::{Alignment, Write};
 
-        let mut out = WriteProxy::new(f, self.escaper.clone());
-        write!(out, "{}", self.item)
+        let limit = f.precision().unwrap_or(usize::MAX);
+        let padding = match f.width() {
+            Some(width) => width.saturating_sub(self.count_limited(limit)?),
+            None => 0,
+        };
+        if padding == 0 {
+            return self.write_limited(f, limit)
+        }
+
+        let (pre, post) = match f.align().unwrap_or(Alignment::Left) {
+            Alignment::Left   => (0, padding),
+            Alignment::Right  => (padding, 0),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+        let fill = f.fill();
+        (0..pre).try_for_each(|_| f.write_char(fill))?;
+        self.write_limited(f, limit)?;
+        (0..post).try_for_each(|_| f.write_char(fill))
+    }
+}
+
+
+/// Clone an [Escaper] and let it perform lookahead on some input, if requested
+pub(crate) fn look_ahead<E: Escaper>(escaper: &E, input: fmt::Arguments<'_>) -> Result<E, fmt::Error> {
+    let mut escaper = escaper.clone();
+    while escaper.start_lookahead() {
+        fmt::write(&mut sink::Inspect::new(&mut escaper), input)?;
     }
+    Ok(escaper)
+}
+
+
+/// Length of some escaped output
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Length {
+    /// Length in bytes of the UTF-8 encoded output
+    pub bytes: usize,
+    /// Length in `char`s
+    pub chars: usize,
+}
+
+
+/// What to count for the width and precision of an [Escaped]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Count {
+    /// Count `char`s of the escaped output
+    #[default]
+    Output,
+    /// Count `char`s of the unescaped input
+    Input,
 }
 
 
-/// Escaping [fmt::Write] implementation
-struct WriteProxy<'a, 'b, E: Escaper> {
-    formatter: &'a mut fmt::Formatter<'b>,
+/// Escaping [fmt::Write] adapter
+///
+/// This type wraps a [fmt::Write] together with an [Escaper]. All `str`s and
+/// `char`s written to it are escaped via the [Escaper] and forwarded to the
+/// inner [fmt::Write]. The same [Escaper] instance is used for all writes,
+/// i.e. state is carried across calls to [write_str](fmt::Write::write_str).
+///
+/// [Escaper::start] is invoked before the first character is processed.
+/// [Escaper::finish] is invoked via [finish](EscapingWriter::finish), which
+/// should be called after the last write.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut writer = rescue_blanket::EscapingWriter::new(String::new(), char::escape_default);
+/// write!(writer, "{}=", "foo").unwrap();
+/// writer.write_str("\"bar\"").unwrap();
+/// assert_eq!(writer.finish().unwrap(), "foo=\\\"bar\\\"");
+/// ```
+#[derive(Clone, Debug)]
+pub struct EscapingWriter<W: fmt::Write, E: Escaper> {
+    inner: W,
     escaper: E,
+    started: bool,
+}
+
+impl<W: fmt::Write, E: Escaper> EscapingWriter<W, E> {
+    /// Create a new writer forwarding to `inner`, escaping via an [Escaper]
+    pub fn new(inner: W, escaper: E) -> Self {
+        Self {inner, escaper, started: false}
+    }
+
+    /// Retrieve a reference to the inner [fmt::Write]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Retrieve a mutable reference to the inner [fmt::Write]
+    ///
+    /// Writing to the inner [fmt::Write] directly bypasses escaping.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Finish escaping and retrieve the inner [fmt::Write]
+    ///
+    /// This function invokes [Escaper::finish], preceded by [Escaper::start] if
+    /// nothing was written yet.
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        self.finish_in_place()?;
+        Ok(self.inner)
+    }
+
+    /// Retrieve the inner [fmt::Write] without finishing escaping
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Finish escaping without consuming the writer
+    pub(crate) fn finish_in_place(&mut self) -> fmt::Result {
+        self.start()?;
+        self.escaper.finish(&mut self.inner)
+    }
+
+    /// Retrieve a [Feed] for writing to the inner [fmt::Write]
+    fn feed(&mut self) -> Feed<'_, E> {
+        Feed {escaper: &mut self.escaper, out: &mut self.inner}
+    }
+
+    /// Invoke [Escaper::start] if it was not invoked yet
+    fn start(&mut self) -> fmt::Result {
+        if !self.started {
+            self.started = true;
+            self.escaper.start(&mut self.inner)?;
+        }
+        Ok(())
+    }
 }
 
-impl<'a, 'b, E: Escaper> WriteProxy<'a, 'b, E> {
-    /// Create a new proxy
-    fn new(formatter: &'a mut fmt::Formatter<'b>, escaper: E) -> Self {
-        Self {formatter, escaper}
+impl<W: fmt::Write, E: Escaper> fmt::Write for EscapingWriter<W, E> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(())
+        }
+        self.start()?;
+        self.feed().write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.start()?;
+        self.feed().write_char(c)
     }
 }
 
-impl<E: Escaper> fmt::Write for WriteProxy<'_, '_, E> {
+
+/// Escaping [fmt::Write] implementation borrowing an [Escaper]
+///
+/// Unlike [EscapingWriter], this type does not invoke [Escaper::start] or
+/// [Escaper::finish].
+pub(crate) struct Feed<'a, E: Escaper> {
+    pub escaper: &'a mut E,
+    pub out: &'a mut dyn fmt::Write,
+}
+
+impl<E: Escaper> fmt::Write for Feed<'_, E> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        s.chars().try_for_each(|c| self.write_char(c))
+        write_runs(self.escaper, self.out, s, E::is_passthrough, E::process_into)
     }
 
     fn write_char(&mut self, c: char) -> fmt::Result {
-        self.escaper.process(c).fmt(self.formatter)
+        self.escaper.process_into(c, self.out)
+    }
+}
+
+
+/// Escape a string, forwarding runs of passthrough characters in one go
+///
+/// Characters for which `is_passthrough` returns `true` are written to `out`
+/// unaltered, all others are handed to `process` individually.
+pub(crate) fn write_runs<E>(
+    escaper: &mut E,
+    out: &mut dyn fmt::Write,
+    s: &str,
+    is_passthrough: impl Fn(&E, char) -> bool,
+    mut process: impl FnMut(&mut E, char, &mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    let mut rest = s;
+    while !rest.is_empty() {
+        let (run, tail) = rest.split_at(
+            rest.find(|c| !is_passthrough(escaper, c)).unwrap_or(rest.len())
+        );
+        if !run.is_empty() {
+            out.write_str(run)?;
+        }
+
+        let mut chars = tail.chars();
+        if let Some(c) = chars.next() {
+            process(escaper, c, out)?;
+        }
+        rest = chars.as_str();
     }
+    Ok(())
 }
 
 
@@ -193,6 +714,14 @@ pub trait Escapable: Display + Sized {
         Escaped::new_default(self)
     }
 
+    /// Wrap this value in a [TryEscaped] for fallibly escaped formatting
+    ///
+    /// The resulting [TryEscaped] will escape the value when being formatted
+    /// via [Display] using the given [TryEscaper].
+    fn try_escaped_with<E: TryEscaper>(self, escaper: E) -> TryEscaped<Self, E> {
+        TryEscaped::new(self, escaper)
+    }
+
     /// Wrap this value in an [Escaped] for escaping with [char::escape_default]
     ///
     /// The resulting [Escaped] will escape the value when being formatted via
@@ -243,6 +772,51 @@ pub trait Escapable: Display + Sized {
     fn escaped_unicode(self) -> Escaped<Self, fn(char) -> core::char::EscapeUnicode> {
         self.escaped_with(char::escape_unicode)
     }
+
+    /// Wrap this value in an [Escaped] for escaping as HTML text content
+    ///
+    /// The resulting [Escaped] will escape the value when being formatted via
+    /// [Display] using [html::Text] as [Escaper].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Escapable;
+    /// assert_eq!("1 < 2 & 3".escaped_html().to_string(), "1 &lt; 2 &amp; 3");
+    /// ```
+    fn escaped_html(self) -> Escaped<Self, html::Text> {
+        self.escaped_with(html::Text)
+    }
+
+    /// Wrap this value in an [Escaped] for escaping as HTML attribute value
+    ///
+    /// The resulting [Escaped] will escape the value when being formatted via
+    /// [Display] using [html::Attribute] as [Escaper].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Escapable;
+    /// assert_eq!("it's \"x\"".escaped_html_attr().to_string(), "it&#39;s &quot;x&quot;");
+    /// ```
+    fn escaped_html_attr(self) -> Escaped<Self, html::Attribute> {
+        self.escaped_with(html::Attribute)
+    }
+
+    /// Wrap this value in an [Escaped] for quoting as POSIX shell word
+    ///
+    /// The resulting [Escaped] will format the value as a single-quoted word
+    /// when being formatted via [Display] using [shell::Quote] as [Escaper].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Escapable;
+    /// assert_eq!("rm -rf '/'".escaped_shell().to_string(), "'rm -rf '\\''/'\\'''");
+    /// ```
+    fn escaped_shell(self) -> Escaped<Self, shell::Quote> {
+        self.escaped_with(shell::Quote)
+    }
 }
 
 impl<T: Display> Escapable for T {
@@ -0,0 +1,190 @@
+//! Escapers for Rust string literals
+//!
+//! This module provides [StringContent], an [Escaper] for the content of Rust
+//! string literals, and [Raw], an [Escaper] producing complete raw string
+//! literals such as `r#"..."#`.
+//!
+//! Unlike [char::escape_debug], [StringContent] guarantees valid literals which
+//! do not trigger any lints, e.g. by escaping Unicode bidirectional formatting
+//! characters.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, rust};
+//! let s = "C:\\\"dir\"\n";
+//! assert_eq!(s.escaped_with(rust::StringContent).to_string(), "C:\\\\\\\"dir\\\"\\n");
+//!
+//! assert_eq!("C:\\dir".escaped_with(rust::Raw::new()).to_string(), "r\"C:\\dir\"");
+//! assert_eq!("say \"#hi\"".escaped_with(rust::Raw::new()).to_string(), "r##\"say \"#hi\"\"##");
+//! assert_eq!("a\rb".escaped_with(rust::Raw::new()).to_string(), "\"a\\rb\"");
+//! ```
+//!
+//! A [Raw] shared between values determines the number of `#` for each value:
+//!
+//! ```
+//! use rescue_blanket::{Escapable, rust};
+//! use std::cell::RefCell;
+//!
+//! let raw = RefCell::new(rust::Raw::new());
+//! assert_eq!("a".escaped_with(&raw).to_string(), "r\"a\"");
+//! assert_eq!("a\"b".escaped_with(&raw).to_string(), "r#\"a\"b\"#");
+//! assert_eq!("a".escaped_with(&raw).to_string(), "r\"a\"");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for Rust string literal content
+///
+/// This escaper escapes `"` and `\` as well as control characters. Control
+/// characters with a short escape sequence (e.g. `\n`) are escaped using that
+/// sequence, all others are escaped as `\u{...}`. The latter also applies to
+/// Unicode bidirectional formatting characters, which are rejected by the Rust
+/// compiler by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringContent;
+
+impl Escaper for StringContent {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '"'  => Output::Short("\\\""),
+            '\\' => Output::Short("\\\\"),
+            '\n' => Output::Short("\\n"),
+            '\r' => Output::Short("\\r"),
+            '\t' => Output::Short("\\t"),
+            '\0' => Output::Short("\\0"),
+            c if c.is_control() || is_bidi_control(c) => Output::Unicode(c),
+            c => Output::Char(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !matches!(input, '"' | '\\') && !input.is_control() && !is_bidi_control(input)
+    }
+}
+
+
+/// [Escaper] producing Rust raw string literals
+///
+/// This escaper encloses the value in a raw string literal with the minimal
+/// number of `#` required for the value's content, which is determined via
+/// [lookahead](Escaper#lookahead). Within, all characters are passed through
+/// unaltered.
+///
+/// If no lookahead is performed, or if the value cannot be represented as raw
+/// string literal, the value is enclosed in an ordinary string literal and
+/// escaped via [StringContent] instead. The latter applies to values
+/// containing carriage returns or Unicode bidirectional formatting characters
+/// as well as values requiring more than 255 `#`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Raw {
+    hashes: Option<usize>,
+    run: Option<usize>,
+    inspected: bool,
+}
+
+impl Raw {
+    /// Create a new escaper for raw string literals
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Escaper for Raw {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match self.hashes {
+            Some(_) => Output::Char(input),
+            None    => StringContent.process(input),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.hashes.is_some() || StringContent.is_passthrough(input)
+    }
+
+    fn start_lookahead(&mut self) -> bool {
+        if self.inspected {
+            return false
+        }
+        self.inspected = true;
+        self.hashes = Some(0);
+        self.run = None;
+        true
+    }
+
+    fn inspect(&mut self, input: char) {
+        let Some(hashes) = self.hashes else { return };
+
+        // A raw string literal ends at a `"` followed by the number of `#` it
+        // started with. We track the number of `#` following each `"`.
+        self.run = match (input, self.run) {
+            ('"', _)       => Some(0),
+            ('#', Some(n)) => Some(n + 1),
+            _              => None,
+        };
+        let required = self.run.map(|n| n + 1).unwrap_or(0).max(hashes);
+
+        self.hashes = if input == '\r' || is_bidi_control(input) || required > 255 {
+            None
+        } else {
+            Some(required)
+        };
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if let Some(hashes) = self.hashes {
+            out.write_char('r')?;
+            (0..hashes).try_for_each(|_| out.write_char('#'))?;
+        }
+        out.write_char('"')
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('"')?;
+        (0..self.hashes.unwrap_or(0)).try_for_each(|_| out.write_char('#'))?;
+
+        // The next value requires its own lookahead
+        *self = Default::default();
+        Ok(())
+    }
+}
+
+
+/// [Output](Escaper::Output) of [StringContent] and [Raw]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A short escape sequence such as `\n`
+    Short(&'static str),
+    /// A `\u{...}` escape sequence for the given character
+    Unicode(char),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)    => f.write_char(*c),
+            Self::Short(s)   => f.write_str(s),
+            Self::Unicode(c) => write!(f, "\\u{{{:x}}}", *c as u32),
+        }
+    }
+}
+
+
+/// Check whether a character is a Unicode bidirectional formatting character
+///
+/// These are the characters rejected in literals by the
+/// `text_direction_codepoint_in_literal` lint.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
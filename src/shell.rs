@@ -0,0 +1,72 @@
+//! Quoting for POSIX shells
+//!
+//! This module provides [Quote], an [Escaper] which formats a value as a
+//! single, single-quoted word for POSIX shells. Within single quotes, no
+//! character has any special meaning apart from `'` itself, which cannot be
+//! escaped. It is thus replaced by `'\''`: a closing quote, an escaped quote
+//! and an opening quote.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! assert_eq!("it's $HOME".escaped_shell().to_string(), "'it'\\''s $HOME'");
+//! assert_eq!("".escaped_shell().to_string(), "''");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for single-quoted shell words
+///
+/// This escaper encloses the value in single quotes via [start](Escaper::start)
+/// and [finish](Escaper::finish). Within, it replaces `'` with `'\''` and
+/// passes through all other characters unaltered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Quote;
+
+impl Escaper for Quote {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '\'' => Output::Quote,
+            c    => Output::Char(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        input != '\''
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('\'')
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('\'')
+    }
+}
+
+
+/// [Output](Escaper::Output) of [Quote]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// An escaped single quote
+    Quote,
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c) => f.write_char(*c),
+            Self::Quote   => f.write_str("'\\''"),
+        }
+    }
+}
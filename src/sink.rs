@@ -0,0 +1,210 @@
+//! Auxiliary [fmt::Write] implementations
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [fmt::Write] implementation counting, but otherwise discarding its input
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Counter {
+    pub bytes: usize,
+    pub chars: usize,
+}
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len();
+        self.chars += s.chars().count();
+        Ok(())
+    }
+}
+
+
+/// [fmt::Write] implementation discarding its input
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+
+/// [fmt::Write] implementation forwarding a limited number of `char`s
+///
+/// Once the limit is exceeded, writing fails. [Limited::result] allows
+/// distinguishing this condition from other errors.
+#[derive(Debug)]
+pub(crate) struct Limited<W: fmt::Write> {
+    inner: W,
+    remaining: usize,
+    exceeded: bool,
+}
+
+impl<W: fmt::Write> Limited<W> {
+    /// Create a new sink forwarding at most `limit` `char`s to `inner`
+    pub fn new(inner: W, limit: usize) -> Self {
+        Self {inner, remaining: limit, exceeded: false}
+    }
+
+    /// Map the result of writing to this sink, treating an exceeded limit as Ok
+    pub fn result(&self, result: fmt::Result) -> fmt::Result {
+        if self.exceeded {
+            Ok(())
+        } else {
+            result
+        }
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for Limited<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.char_indices().nth(self.remaining) {
+            Some((pos, _)) => {
+                self.inner.write_str(&s[..pos])?;
+                self.remaining = 0;
+                self.exceeded = true;
+                Err(fmt::Error)
+            },
+            None => {
+                self.remaining -= s.chars().count();
+                self.inner.write_str(s)
+            },
+        }
+    }
+}
+
+
+/// [fmt::Write] implementation retaining only the `n`th `char`
+///
+/// Writing fails once the `n`th `char` was written.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Nth {
+    remaining: usize,
+    found: Option<char>,
+}
+
+impl Nth {
+    /// Create a new sink retaining the `n`th `char`
+    pub fn new(n: usize) -> Self {
+        Self {remaining: n, found: None}
+    }
+
+    /// Retrieve the `n`th `char`, if it was written
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+}
+
+impl fmt::Write for Nth {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match self.remaining.checked_sub(1) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                Ok(())
+            },
+            None => {
+                self.found = Some(c);
+                Err(fmt::Error)
+            },
+        }
+    }
+}
+
+
+/// [fmt::Write] implementation expecting a specific `char`, or nothing
+///
+/// Writing anything but the expected `char` fails.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Expect {
+    expected: Option<char>,
+}
+
+impl Expect {
+    /// Create a new sink expecting the given `char`, or nothing
+    pub fn new(expected: Option<char>) -> Self {
+        Self {expected}
+    }
+
+    /// Check whether the result of writing matched the expectation
+    pub fn matched(&self, result: fmt::Result) -> bool {
+        result.is_ok() && self.expected.is_none()
+    }
+}
+
+impl fmt::Write for Expect {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match self.expected.take() {
+            Some(e) if e == c => Ok(()),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+
+/// [fmt::Write] implementation checking whether an [Escaper] alters its input
+///
+/// Writing fails as soon as the [Escaper] would produce output different from
+/// the input.
+#[derive(Debug)]
+pub(crate) struct Compare<'a, E: Escaper> {
+    escaper: &'a mut E,
+}
+
+impl<'a, E: Escaper> Compare<'a, E> {
+    /// Create a new sink checking the given [Escaper]
+    pub fn new(escaper: &'a mut E) -> Self {
+        Self {escaper}
+    }
+}
+
+impl<E: Escaper> fmt::Write for Compare<'_, E> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        if self.escaper.is_passthrough(c) {
+            return Ok(())
+        }
+
+        let mut expect = Expect::new(Some(c));
+        let res = self.escaper.process_into(c, &mut expect);
+        if expect.matched(res) {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+
+/// [fmt::Write] implementation passing its input to [Escaper::inspect]
+#[derive(Debug)]
+pub(crate) struct Inspect<'a, E: Escaper> {
+    escaper: &'a mut E,
+}
+
+impl<'a, E: Escaper> Inspect<'a, E> {
+    /// Create a new sink passing its input to the given [Escaper]
+    pub fn new(escaper: &'a mut E) -> Self {
+        Self {escaper}
+    }
+}
+
+impl<E: Escaper> fmt::Write for Inspect<'_, E> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().for_each(|c| self.escaper.inspect(c));
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! Quoting for SQL
+//!
+//! This module provides [Escaper]s for SQL [StringLiteral]s and quoted
+//! [Identifier]s. Both enclose the value in the appropriate quotes and escape
+//! it according to a [Dialect].
+//!
+//! Whenever possible, users should bind values as parameters rather than
+//! formatting them into statements. These escapers are meant for cases where
+//! this is not possible, e.g. for table names or in DDL statements.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! use rescue_blanket::sql::{Dialect, Identifier, StringLiteral};
+//!
+//! let s = "it's C:\\";
+//! assert_eq!(s.escaped_with(StringLiteral::default()).to_string(), "'it''s C:\\'");
+//! assert_eq!(s.escaped_with(StringLiteral::new(Dialect::MySql)).to_string(), "'it''s C:\\\\'");
+//! assert_eq!(s.escaped_with(StringLiteral::new(Dialect::Postgres)).to_string(), "E'it''s C:\\\\'");
+//!
+//! let s = "my \"table\"]";
+//! assert_eq!(s.escaped_with(Identifier::default()).to_string(), "\"my \"\"table\"\"]\"");
+//! assert_eq!(s.escaped_with(Identifier::new(Dialect::MySql)).to_string(), "`my \"table\"]`");
+//! assert_eq!(s.escaped_with(Identifier::new(Dialect::SqlServer)).to_string(), "[my \"table\"]]]");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for SQL string literals
+///
+/// This escaper encloses the value in single quotes and doubles any `'`
+/// within. Depending on the [Dialect], backslashes are doubled as well.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StringLiteral {
+    dialect: Dialect,
+}
+
+impl StringLiteral {
+    /// Create a new escaper for the given [Dialect]
+    pub fn new(dialect: Dialect) -> Self {
+        Self {dialect}
+    }
+
+    /// Check whether backslashes need to be escaped
+    fn escape_backslash(&self) -> bool {
+        matches!(self.dialect, Dialect::MySql | Dialect::Postgres)
+    }
+}
+
+impl Escaper for StringLiteral {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '\'' => Output::Doubled(input),
+            '\\' if self.escape_backslash() => Output::Doubled(input),
+            c => Output::Char(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        input != '\'' && (input != '\\' || !self.escape_backslash())
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        if self.dialect == Dialect::Postgres {
+            out.write_char('E')?;
+        }
+        out.write_char('\'')
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char('\'')
+    }
+}
+
+
+/// [Escaper] for quoted SQL identifiers
+///
+/// This escaper encloses the value in the identifier quotes of the [Dialect]
+/// and doubles any closing quote within.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Identifier {
+    dialect: Dialect,
+}
+
+impl Identifier {
+    /// Create a new escaper for the given [Dialect]
+    pub fn new(dialect: Dialect) -> Self {
+        Self {dialect}
+    }
+
+    /// Retrieve the opening and closing quote
+    fn quotes(&self) -> (char, char) {
+        match self.dialect {
+            Dialect::Standard | Dialect::Postgres => ('"', '"'),
+            Dialect::MySql                        => ('`', '`'),
+            Dialect::SqlServer                    => ('[', ']'),
+        }
+    }
+}
+
+impl Escaper for Identifier {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        if input == self.quotes().1 {
+            Output::Doubled(input)
+        } else {
+            Output::Char(input)
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        input != self.quotes().1
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char(self.quotes().0)
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char(self.quotes().1)
+    }
+}
+
+
+/// SQL dialect
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// Standard SQL
+    ///
+    /// String literals are enclosed in `'`, identifiers in `"`. Backslashes
+    /// have no special meaning. This dialect is suitable for e.g. SQLite and
+    /// for PostgreSQL with `standard_conforming_strings` enabled.
+    #[default]
+    Standard,
+    /// MySQL and MariaDB
+    ///
+    /// Backslashes in string literals are escaped, which is incorrect if the
+    /// `NO_BACKSLASH_ESCAPES` SQL mode is enabled. Identifiers are enclosed in
+    /// backticks.
+    MySql,
+    /// PostgreSQL
+    ///
+    /// String literals are written as escape string constants, e.g. `E'...'`,
+    /// in which backslashes are escaped. Unlike ordinary string constants,
+    /// these do not depend on `standard_conforming_strings`. Identifiers are
+    /// enclosed in `"`.
+    Postgres,
+    /// Microsoft SQL Server
+    ///
+    /// String literals are treated as in [Standard](Dialect::Standard) SQL.
+    /// Identifiers are enclosed in `[` and `]`.
+    SqlServer,
+}
+
+
+/// [Output](Escaper::Output) of [StringLiteral] and [Identifier]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// The input character, escaped by doubling it
+    Doubled(char),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)    => f.write_char(*c),
+            Self::Doubled(c) => {
+                f.write_char(*c)?;
+                f.write_char(*c)
+            },
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Table-driven escaping
+//!
+//! Many escaping schemes boil down to replacing a few ASCII characters with
+//! fixed strings while passing through everything else. This module provides
+//! [TableEscaper], an [Escaper] implementing such schemes based on a lookup
+//! table which can be constructed in a `const` context.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, TableEscaper};
+//!
+//! static LATEX: TableEscaper = TableEscaper::new(&[('&', "\\&"), ('%', "\\%"), ('_', "\\_")]);
+//!
+//! assert_eq!("100% R&D_budget".escaped_with(&LATEX).to_string(), "100\\% R\\&D\\_budget");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] replacing ASCII characters based on a lookup table
+///
+/// This escaper replaces ASCII characters with `&'static str`s according to a
+/// table with one entry per ASCII character. Characters without an entry and
+/// non-ASCII characters are passed through unaltered.
+///
+/// As the table is comparatively large, users may prefer defining a `static`
+/// table and using a reference to it as [Escaper].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TableEscaper {
+    table: [Option<&'static str>; 128],
+}
+
+impl TableEscaper {
+    /// Create a new escaper from a list of replacements
+    ///
+    /// # Panics
+    ///
+    /// This function panics if any of the characters to replace is not ASCII.
+    pub const fn new(replacements: &[(char, &'static str)]) -> Self {
+        let mut table = [None; 128];
+        let mut i = 0;
+        while i < replacements.len() {
+            let (c, replacement) = replacements[i];
+            assert!(c.is_ascii(), "only ASCII characters may be replaced");
+            table[c as usize] = Some(replacement);
+            i += 1;
+        }
+        Self {table}
+    }
+
+    /// Retrieve the replacement for a given character, if any
+    pub const fn replacement(&self, input: char) -> Option<&'static str> {
+        if input.is_ascii() {
+            self.table[input as usize]
+        } else {
+            None
+        }
+    }
+}
+
+impl Escaper for TableEscaper {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        (&*self).process(input)
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.replacement(input).is_none()
+    }
+}
+
+impl Escaper for &TableEscaper {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match self.replacement(input) {
+            Some(r) => Output::Replacement(r),
+            None    => Output::Char(input),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.replacement(input).is_none()
+    }
+}
+
+
+/// [Output](Escaper::Output) of [TableEscaper]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// The replacement for the input character
+    Replacement(&'static str),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)        => f.write_char(*c),
+            Self::Replacement(r) => f.write_str(r),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Truncation of escaped output
+//!
+//! When limiting the size of escaped output, e.g. of log lines or header
+//! values, simply cutting off the output may leave an incomplete escape
+//! sequence. This module provides [Truncated], a wrapper around an
+//! [Escaped] which truncates its output only between complete
+//! [Output](Escaper::Output)s.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! use rescue_blanket::truncate::Budget;
+//!
+//! let escaped = "a & b & c".escaped_html();
+//! assert_eq!(escaped.truncated(Budget::Bytes(20)).to_string(), "a &amp; b &amp; c");
+//! assert_eq!(escaped.truncated(Budget::Bytes(10)).to_string(), "a &amp; b ");
+//! assert_eq!(escaped.truncated(Budget::Chars(10)).with_marker("…").to_string(), "a &amp; b…");
+//!
+//! let escaped = "it's".escaped_shell();
+//! assert_eq!(escaped.truncated(Budget::Chars(7)).to_string(), "'it'");
+//! ```
+
+use core::fmt::{self, Display, Write};
+
+use crate::{Escaped, Escaper, sink};
+
+
+/// Wrapper for truncating escaped output
+///
+/// This type wraps an [Escaped] together with a [Budget]. When displayed via
+/// its own implementation of [Display], the output of the [Escaped] is
+/// truncated such that it does not exceed the budget. Truncation occurs only
+/// between complete [Output](Escaper::Output)s of the [Escaper]. The output of
+/// [Escaper::finish] is taken into account and included, unless it does not
+/// fit together with the output of [Escaper::start]. In that case, nothing but
+/// the marker is displayed.
+///
+/// Optionally, a marker may be appended if the output was truncated. The
+/// marker counts towards the budget. If the marker alone exceeds the budget,
+/// it is omitted.
+///
+/// Formatting flags such as width and precision are not considered.
+///
+/// # Examples
+///
+/// ```
+/// use rescue_blanket::Escapable;
+/// use rescue_blanket::truncate::Budget;
+///
+/// let escaped = "abc".escaped_html();
+/// assert_eq!(escaped.truncated(Budget::Chars(0)).with_marker("…").to_string(), "");
+/// assert_eq!(escaped.truncated(Budget::Chars(1)).with_marker("...").to_string(), "a");
+/// assert_eq!(escaped.truncated(Budget::Chars(2)).with_marker("…").to_string(), "a…");
+///
+/// let escaped = "abc".escaped_shell();
+/// assert_eq!(escaped.truncated(Budget::Chars(1)).to_string(), "");
+/// assert_eq!(escaped.truncated(Budget::Chars(2)).to_string(), "''");
+/// assert_eq!(escaped.truncated(Budget::Chars(2)).with_marker("…").to_string(), "…");
+/// assert_eq!(escaped.truncated(Budget::Chars(3)).with_marker("…").to_string(), "''…");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Truncated<I: Display, E: Escaper> {
+    escaped: Escaped<I, E>,
+    budget: Budget,
+    marker: &'static str,
+}
+
+impl<I: Display, E: Escaper> Truncated<I, E> {
+    /// Create a new wrapper for the given [Escaped] with the given [Budget]
+    pub fn new(escaped: Escaped<I, E>, budget: Budget) -> Self {
+        Self {escaped, budget, marker: ""}
+    }
+
+    /// Set a marker to append if the output is truncated
+    pub fn with_marker(self, marker: &'static str) -> Self {
+        Self {marker, ..self}
+    }
+}
+
+impl<I: Display, E: Escaper> Display for Truncated<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_truncated(&self.escaped, f, self.budget, self.marker)
+    }
+}
+
+
+/// Write an [Escaped], truncated to a [Budget]
+///
+/// The `marker` is appended if the output is truncated.
+pub(crate) fn write_truncated<I: Display, E: Escaper>(
+    escaped: &Escaped<I, E>,
+    f: &mut dyn fmt::Write,
+    budget: Budget,
+    marker: &str,
+) -> fmt::Result {
+    let total = escaped.escaped_len()?;
+    if budget.measure(total.bytes, total.chars) <= budget.limit() {
+        return escaped.write_escaped(f)
+    }
+
+    // A marker which does not fit on its own is dropped
+    let mut marker_len = budget.measure(marker.len(), marker.chars().count());
+    let marker = if marker_len <= budget.limit() {
+        marker
+    } else {
+        marker_len = 0;
+        ""
+    };
+
+    let mut out = TruncatingWriter {
+        escaper: escaped.prepared_escaper()?,
+        out: f,
+        budget,
+        remaining: budget.limit().saturating_sub(marker_len),
+        exhausted: false,
+    };
+    if !out.step(|e, w| e.start(w))? {
+        return f.write_str(marker)
+    }
+
+    let res = write!(out, "{}", escaped.item);
+    if !out.exhausted {
+        res?;
+    }
+    out.escaper.finish(out.out)?;
+    f.write_str(marker)
+}
+
+
+/// Budget for the output of a [Truncated]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Budget {
+    /// Maximum number of bytes of the UTF-8 encoded output
+    Bytes(usize),
+    /// Maximum number of `char`s of the output
+    Chars(usize),
+}
+
+impl Budget {
+    /// Retrieve the limit
+    fn limit(self) -> usize {
+        match self {
+            Self::Bytes(n) => n,
+            Self::Chars(n) => n,
+        }
+    }
+
+    /// Select the relevant measure from a number of bytes and `char`s
+    fn measure(self, bytes: usize, chars: usize) -> usize {
+        match self {
+            Self::Bytes(_) => bytes,
+            Self::Chars(_) => chars,
+        }
+    }
+}
+
+
+/// [fmt::Write] escaping its input as long as the budget permits
+struct TruncatingWriter<'a, E: Escaper> {
+    escaper: E,
+    out: &'a mut dyn fmt::Write,
+    budget: Budget,
+    remaining: usize,
+    exhausted: bool,
+}
+
+impl<E: Escaper> TruncatingWriter<'_, E> {
+    /// Perform an operation on the escaper if its output fits
+    ///
+    /// The output of the operation fits if the remaining budget accommodates
+    /// both its output and the output of [Escaper::finish] afterwards.
+    fn step(
+        &mut self,
+        mut op: impl FnMut(&mut E, &mut dyn fmt::Write) -> fmt::Result,
+    ) -> Result<bool, fmt::Error> {
+        let mut trial = self.escaper.clone();
+        let unit = self.measure(|w| op(&mut trial, w))?;
+        let finish = self.measure(|w| trial.finish(w))?;
+        if unit + finish > self.remaining {
+            self.exhausted = true;
+            return Ok(false)
+        }
+
+        op(&mut self.escaper, self.out)?;
+        self.remaining -= unit;
+        Ok(true)
+    }
+
+    /// Measure the output of some function according to the budget
+    fn measure(&self, f: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result) -> Result<usize, fmt::Error> {
+        let mut counter = sink::Counter::default();
+        f(&mut counter)?;
+        Ok(self.budget.measure(counter.bytes, counter.chars))
+    }
+}
+
+impl<E: Escaper> fmt::Write for TruncatingWriter<'_, E> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        if self.step(|e, w| e.process_into(c, w))? {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Unescape values while they are being formatted
+//!
+//! This module provides the counterpart of [Escaper](crate::Escaper) and
+//! [Escaped](crate::Escaped): [Unescaper] and [Unescaped]. Like its escaping
+//! counterpart, [Unescaped] is a wrapper implementing [Display] such that the
+//! inner value is automatically unescaped when formatted.
+//!
+//! [CharEscapes] reverses the escaping performed by [char::escape_default],
+//! [char::escape_debug] and [char::escape_unicode].
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, Unescapable};
+//! let s = "foo=\"bar\"\n";
+//! assert_eq!(s.escaped_default().unescaped_default().to_string(), s);
+//! assert_eq!(s.escaped_unicode().unescaped_unicode().to_string(), s);
+//! ```
+
+use core::fmt::{self, Display};
+
+
+/// Character-wise processor implementing some unescaping logic
+///
+/// Types implementing this trait define how a string or value is unescaped.
+/// Unescaping logic is usually a state machine driven by the input `char`s:
+/// each invocation of [process](Unescaper::process) receives one character and
+/// produces an [Output](Unescaper::Output) displaying as zero or more `char`s.
+/// For example, the first characters of an escape sequence will usually not
+/// produce any output while the last character will produce the unescaped
+/// character.
+///
+/// If the input is not correctly escaped, [process](Unescaper::process) or
+/// [finish](Unescaper::finish) report a [Malformed] sequence.
+///
+/// # Note
+///
+/// An `Unescaper` needs to implement [Clone]. However, unescaping of a single
+/// string or value is to be performed on the same instance. Clones do not
+/// expected to share any state.
+pub trait Unescaper: Clone {
+    /// Partial output after unescaping
+    ///
+    /// This type represents the output of processing a single input `char`.
+    type Output: Display;
+
+    /// Process a single input character
+    ///
+    /// This function processes a single input `char` and produces as a result
+    /// an appropriate [Output](Unescaper::Output), or a [Malformed] error if
+    /// the character is not valid at this point.
+    fn process(&mut self, input: char) -> Result<Self::Output, Malformed>;
+
+    /// Finish processing a string or value
+    ///
+    /// This function is called once after the last character of a string or
+    /// value was [process](Unescaper::process)ed. It may produce some final
+    /// output or report a [Malformed] error, e.g. if the input ended in the
+    /// middle of an escape sequence. The default implementation does nothing.
+    fn finish(&mut self) -> Result<Option<Self::Output>, Malformed> {
+        Ok(None)
+    }
+}
+
+
+/// Wrapper for unescaping items during formatting
+///
+/// This type wraps an item implementing [Display] together with an
+/// [Unescaper]. When displayed via its own implementation of [Display], the
+/// encapsulated item will be unescaped via the [Unescaper] during the
+/// formatting process. If the item turns out to be malformed, formatting fails
+/// with a [fmt::Error].
+///
+/// # Examples
+///
+/// ```
+/// use rescue_blanket::unescape::{CharEscapes, Unescaped};
+/// let unescaped = Unescaped::new("foo=\\\"bar\\\"", CharEscapes::default());
+/// assert_eq!(unescaped.to_string(), "foo=\"bar\"");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Unescaped<I: Display, U: Unescaper> {
+    item: I,
+    unescaper: U,
+}
+
+impl<I: Display, U: Unescaper> Unescaped<I, U> {
+    /// Create a new wrapper for the given item with an [Unescaper]
+    pub fn new(item: I, unescaper: U) -> Self {
+        Self {item, unescaper}
+    }
+
+    /// Create a new wrapper for the given item with a default [Unescaper]
+    pub fn new_default(item: I) -> Self where U: Default {
+        Self {item, unescaper: Default::default()}
+    }
+}
+
+impl<I: Display, U: Unescaper + Default> From<I> for Unescaped<I, U> {
+    fn from(item: I) -> Self {
+        Self::new_default(item)
+    }
+}
+
+impl<I: Display, U: Unescaper> Display for Unescaped<I, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        let mut out = WriteProxy {formatter: f, unescaper: self.unescaper.clone()};
+        write!(out, "{}", self.item)?;
+        match out.unescaper.finish()? {
+            Some(output) => output.fmt(out.formatter),
+            None => Ok(()),
+        }
+    }
+}
+
+
+/// Unescaping [fmt::Write] implementation
+struct WriteProxy<'a, 'b, U: Unescaper> {
+    formatter: &'a mut fmt::Formatter<'b>,
+    unescaper: U,
+}
+
+impl<U: Unescaper> fmt::Write for WriteProxy<'_, '_, U> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.unescaper.process(c)?.fmt(self.formatter)
+    }
+}
+
+
+/// Convenience trait for unescaping items
+///
+/// This trait augments types implementing [Display] with functions for wrapping
+/// them in instances of [Unescaped], which will unescape the value when being
+/// formatted.
+pub trait Unescapable: Display + Sized {
+    /// Wrap this value in an [Unescaped] for unescaped formatting
+    ///
+    /// The resulting [Unescaped] will unescape the value when being formatted
+    /// via [Display] using the given [Unescaper].
+    fn unescaped_with<U: Unescaper>(self, unescaper: U) -> Unescaped<Self, U>;
+
+    /// Wrap this value in an [Unescaped] for unescaped formatting
+    ///
+    /// The resulting [Unescaped] will unescape the value when being formatted
+    /// via [Display] using the given [Unescaper].
+    fn unescaped_with_default<U: Unescaper + Default>(self) -> Unescaped<Self, U> {
+        Unescaped::new_default(self)
+    }
+
+    /// Wrap this value in an [Unescaped] reversing [char::escape_default]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Unescapable;
+    /// assert_eq!("\\u{e4}\\t\\'".unescaped_default().to_string(), "ä\t'");
+    /// ```
+    fn unescaped_default(self) -> Unescaped<Self, CharEscapes> {
+        self.unescaped_with(CharEscapes::default())
+    }
+
+    /// Wrap this value in an [Unescaped] reversing [char::escape_debug]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Unescapable;
+    /// assert_eq!("ä\\0\\u{301}".unescaped_debug().to_string(), "ä\0\u{301}");
+    /// ```
+    fn unescaped_debug(self) -> Unescaped<Self, CharEscapes> {
+        self.unescaped_with(CharEscapes::default())
+    }
+
+    /// Wrap this value in an [Unescaped] reversing [char::escape_unicode]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rescue_blanket::Unescapable;
+    /// assert_eq!("\\u{61}\\u{2603}".unescaped_unicode().to_string(), "a☃");
+    /// ```
+    fn unescaped_unicode(self) -> Unescaped<Self, CharEscapes> {
+        self.unescaped_with(CharEscapes::default())
+    }
+}
+
+impl<T: Display> Unescapable for T {
+    fn unescaped_with<U: Unescaper>(self, unescaper: U) -> Unescaped<Self, U> {
+        Unescaped::new(self, unescaper)
+    }
+}
+
+
+/// [Unescaper] for Rust-style character escapes
+///
+/// This unescaper reverses the escaping performed by [char::escape_default],
+/// [char::escape_debug] and [char::escape_unicode]. It accepts the escape
+/// sequences `\t`, `\r`, `\n`, `\'`, `\"`, `\\`, `\0` and `\u{...}` with one to
+/// six hexadecimal digits. All other characters are passed through unaltered.
+///
+/// # Examples
+///
+/// ```
+/// use rescue_blanket::Unescapable;
+/// use std::fmt::Write;
+///
+/// let mut out = String::new();
+/// assert!(write!(out, "{}", "\\x41".unescaped_default()).is_err());
+/// assert!(write!(out, "{}", "\\u{110000}".unescaped_default()).is_err());
+/// assert!(write!(out, "{}", "trailing\\".unescaped_default()).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CharEscapes {
+    state: State,
+}
+
+impl Unescaper for CharEscapes {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Result<Self::Output, Malformed> {
+        let (state, output) = match (self.state, input) {
+            (State::Plain, '\\') => (State::Backslash, Output::Nothing),
+            (State::Plain, c) => (State::Plain, Output::Char(c)),
+            (State::Backslash, 'u') => (State::Unicode, Output::Nothing),
+            (State::Backslash, c) => {
+                let c = match c {
+                    't' => '\t',
+                    'r' => '\r',
+                    'n' => '\n',
+                    '0' => '\0',
+                    '\'' | '"' | '\\' => c,
+                    _ => return Err(Malformed),
+                };
+                (State::Plain, Output::Char(c))
+            },
+            (State::Unicode, '{') => (State::Digits(0, 0), Output::Nothing),
+            (State::Digits(value, digits), '}') if digits > 0 => {
+                let c = char::from_u32(value).ok_or(Malformed)?;
+                (State::Plain, Output::Char(c))
+            },
+            (State::Digits(value, digits), c) if digits < 6 => {
+                let digit = c.to_digit(16).ok_or(Malformed)?;
+                (State::Digits(value * 16 + digit, digits + 1), Output::Nothing)
+            },
+            _ => return Err(Malformed),
+        };
+        self.state = state;
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> Result<Option<Self::Output>, Malformed> {
+        match self.state {
+            State::Plain => Ok(None),
+            _ => Err(Malformed),
+        }
+    }
+}
+
+
+/// State of [CharEscapes]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum State {
+    /// Outside of any escape sequence
+    #[default]
+    Plain,
+    /// After a backslash
+    Backslash,
+    /// After `\u`
+    Unicode,
+    /// Within the braces of `\u{...}`, with the value and number of digits
+    Digits(u32, u8),
+}
+
+
+/// [Output](Unescaper::Output) of [CharEscapes]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// No output, e.g. within an escape sequence
+    Nothing,
+    /// A single, unescaped character
+    Char(char),
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Nothing => Ok(()),
+            Self::Char(c) => f.write_char(*c),
+        }
+    }
+}
+
+
+/// Error indicating a malformed escape sequence
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Malformed;
+
+impl Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("malformed escape sequence")
+    }
+}
+
+impl From<Malformed> for fmt::Error {
+    fn from(_: Malformed) -> Self {
+        fmt::Error
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Malformed {}
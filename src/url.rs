@@ -0,0 +1,181 @@
+//! Percent-encoding of URL components
+//!
+//! This module provides [Encode], an [Escaper] for components of URLs as
+//! specified in [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986). Characters
+//! not contained in a [Set] of allowed ASCII characters are percent-encoded,
+//! i.e. each byte of their UTF-8 encoding is written as `%XX`.
+//!
+//! Predefined [Set]s are provided for path segments, query keys and values,
+//! fragments and `application/x-www-form-urlencoded` data, which encodes space
+//! as `+`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::Escapable;
+//! use rescue_blanket::url::{Encode, Set};
+//!
+//! let url = format!(
+//!     "https://example.com/{}?{}={}#{}",
+//!     "a/b c".escaped_with(Encode::new(Set::PATH_SEGMENT)),
+//!     "q&a".escaped_with(Encode::new(Set::QUERY)),
+//!     "1+1=2".escaped_with(Encode::new(Set::QUERY)),
+//!     "grüße?".escaped_with(Encode::new(Set::FRAGMENT)),
+//! );
+//! assert_eq!(url, "https://example.com/a%2Fb%20c?q%26a=1%2B1%3D2#gr%C3%BC%C3%9Fe?");
+//!
+//! assert_eq!("a b&c".escaped_with(Encode::new(Set::FORM)).to_string(), "a+b%26c");
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] percent-encoding characters not contained in a [Set]
+///
+/// By default, all characters but the unreserved ones are encoded.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Encode {
+    set: Set,
+}
+
+impl Encode {
+    /// Create a new escaper passing through the characters of the given [Set]
+    pub const fn new(set: Set) -> Self {
+        Self {set}
+    }
+}
+
+impl Escaper for Encode {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        if self.set.contains(input) {
+            Output::Char(input)
+        } else if input == ' ' && self.set.space_as_plus {
+            Output::Plus
+        } else {
+            let mut bytes = [0; 4];
+            let len = input.encode_utf8(&mut bytes).len();
+            Output::Encoded {bytes, len}
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        self.set.contains(input)
+    }
+}
+
+
+/// Set of ASCII characters passed through unaltered by [Encode]
+///
+/// A set may be constructed in a `const` context from one of the predefined
+/// sets via [with](Set::with) and [without](Set::without).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Set {
+    passthrough: u128,
+    space_as_plus: bool,
+}
+
+impl Set {
+    /// Unreserved characters: ASCII letters and digits as well as `-._~`
+    pub const UNRESERVED: Self = Self {passthrough: 0, space_as_plus: false}
+        .with("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~");
+
+    /// Characters allowed in a path segment
+    ///
+    /// In addition to the unreserved characters, this set contains the
+    /// sub-delimiters `!$&'()*+,;=` as well as `:` and `@`.
+    pub const PATH_SEGMENT: Self = Self::UNRESERVED.with("!$&'()*+,;=:@");
+
+    /// Characters allowed in a query key or value
+    ///
+    /// In addition to the unreserved characters, this set contains `!$'()*,:@/?`.
+    /// Characters commonly used for separating keys and values, `&=;+`, are
+    /// excluded.
+    pub const QUERY: Self = Self::UNRESERVED.with("!$'()*,:@/?");
+
+    /// Characters allowed in a fragment
+    ///
+    /// In addition to the unreserved characters, this set contains the
+    /// sub-delimiters `!$&'()*+,;=` as well as `:@/?`.
+    pub const FRAGMENT: Self = Self::PATH_SEGMENT.with("/?");
+
+    /// Characters allowed in `application/x-www-form-urlencoded` data
+    ///
+    /// This set contains ASCII letters and digits as well as `*-._`. Space is
+    /// encoded as `+`.
+    pub const FORM: Self = Self::UNRESERVED.without("~").with("*").space_as_plus(true);
+
+    /// Add the given characters to this set
+    ///
+    /// # Panics
+    ///
+    /// This function panics if any of the characters is not ASCII.
+    pub const fn with(self, chars: &str) -> Self {
+        Self {passthrough: self.passthrough | Self::mask(chars), ..self}
+    }
+
+    /// Remove the given characters from this set
+    ///
+    /// # Panics
+    ///
+    /// This function panics if any of the characters is not ASCII.
+    pub const fn without(self, chars: &str) -> Self {
+        Self {passthrough: self.passthrough & !Self::mask(chars), ..self}
+    }
+
+    /// Set whether space is encoded as `+` if it is not contained in this set
+    pub const fn space_as_plus(self, space_as_plus: bool) -> Self {
+        Self {space_as_plus, ..self}
+    }
+
+    /// Check whether the given character is contained in this set
+    pub const fn contains(&self, input: char) -> bool {
+        input.is_ascii() && self.passthrough & (1 << input as u32) != 0
+    }
+
+    /// Compute the bit mask for a number of ASCII characters
+    const fn mask(chars: &str) -> u128 {
+        let bytes = chars.as_bytes();
+        let mut mask = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(bytes[i].is_ascii(), "only ASCII characters may be contained");
+            mask |= 1 << bytes[i];
+            i += 1;
+        }
+        mask
+    }
+}
+
+impl Default for Set {
+    fn default() -> Self {
+        Self::UNRESERVED
+    }
+}
+
+
+/// [Output](Escaper::Output) of [Encode]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A space encoded as `+`
+    Plus,
+    /// The UTF-8 encoding of the input character, to be percent-encoded
+    Encoded {bytes: [u8; 4], len: usize},
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)              => f.write_char(*c),
+            Self::Plus                 => f.write_char('+'),
+            Self::Encoded {bytes, len} => bytes[..*len].iter().try_for_each(|b| write!(f, "%{:02X}", b)),
+        }
+    }
+}
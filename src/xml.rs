@@ -0,0 +1,159 @@
+//! Escapers for XML 1.0
+//!
+//! This module provides [Escaper]s for XML [Text] content and quoted
+//! [Attribute] values. Unlike HTML, XML 1.0 does not allow all characters to
+//! occur in a document, not even as character references. Most notably, this
+//! includes most C0 control characters. How such characters are treated is
+//! determined by a [Forbidden] policy.
+//!
+//! # Examples
+//!
+//! ```
+//! use rescue_blanket::{Escapable, xml};
+//! let s = "a < b\u{0}";
+//! assert_eq!(s.escaped_with(xml::Text::default()).to_string(), "a &lt; b\u{FFFD}");
+//! assert_eq!(s.escaped_with(xml::Text::new(xml::Forbidden::Drop)).to_string(), "a &lt; b");
+//!
+//! use std::fmt::Write;
+//! let mut out = String::new();
+//! let escaped = s.escaped_with(xml::Text::new(xml::Forbidden::Fail));
+//! assert!(write!(out, "{}", escaped).is_err());
+//! ```
+
+use core::fmt;
+
+use crate::Escaper;
+
+
+/// [Escaper] for XML text content
+///
+/// This escaper replaces `&`, `<` and `>` with their respective entity
+/// references. Carriage returns are replaced by character references in order
+/// to survive end-of-line normalization. Characters which are not allowed in
+/// XML 1.0 documents are treated according to the [Forbidden] policy.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Text {
+    forbidden: Forbidden,
+}
+
+impl Text {
+    /// Create a new escaper with the given [Forbidden] policy
+    pub fn new(forbidden: Forbidden) -> Self {
+        Self {forbidden}
+    }
+}
+
+impl Escaper for Text {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '&'  => Output::Reference("&amp;"),
+            '<'  => Output::Reference("&lt;"),
+            '>'  => Output::Reference("&gt;"),
+            '\r' => Output::Reference("&#13;"),
+            c if is_allowed(c) => Output::Char(c),
+            _ => self.forbidden.output(),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !matches!(input, '&' | '<' | '>' | '\r') && is_allowed(input)
+    }
+}
+
+
+/// [Escaper] for quoted XML attribute values
+///
+/// In addition to the characters escaped by [Text], this escaper also replaces
+/// `"` and `'` as well as tabs and line feeds with references. The latter are
+/// subject to attribute-value normalization otherwise. The output is suitable
+/// for attribute values quoted with either double or single quotes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attribute {
+    forbidden: Forbidden,
+}
+
+impl Attribute {
+    /// Create a new escaper with the given [Forbidden] policy
+    pub fn new(forbidden: Forbidden) -> Self {
+        Self {forbidden}
+    }
+}
+
+impl Escaper for Attribute {
+    type Output = Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        match input {
+            '"'  => Output::Reference("&quot;"),
+            '\'' => Output::Reference("&apos;"),
+            '\t' => Output::Reference("&#9;"),
+            '\n' => Output::Reference("&#10;"),
+            c    => Text::new(self.forbidden).process(c),
+        }
+    }
+
+    fn is_passthrough(&self, input: char) -> bool {
+        !matches!(input, '"' | '\'' | '\t' | '\n') && Text::new(self.forbidden).is_passthrough(input)
+    }
+}
+
+
+/// Policy for characters which cannot be represented in XML 1.0
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Forbidden {
+    /// Silently drop the character
+    Drop,
+    /// Replace the character with U+FFFD REPLACEMENT CHARACTER
+    #[default]
+    Replace,
+    /// Fail formatting with a [fmt::Error]
+    Fail,
+}
+
+impl Forbidden {
+    /// Retrieve the [Output] for a forbidden character
+    fn output(self) -> Output {
+        match self {
+            Self::Drop    => Output::Nothing,
+            Self::Replace => Output::Char(char::REPLACEMENT_CHARACTER),
+            Self::Fail    => Output::Invalid,
+        }
+    }
+}
+
+
+/// [Output](Escaper::Output) of the XML [Escaper]s
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The input character, passed through unaltered
+    Char(char),
+    /// A reference replacing the input character
+    Reference(&'static str),
+    /// No output, for dropped characters
+    Nothing,
+    /// A character which cannot be represented, failing formatting
+    Invalid,
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Self::Char(c)      => f.write_char(*c),
+            Self::Reference(r) => f.write_str(r),
+            Self::Nothing      => Ok(()),
+            Self::Invalid      => Err(fmt::Error),
+        }
+    }
+}
+
+
+/// Check whether a character may appear in an XML 1.0 document
+///
+/// See <https://www.w3.org/TR/xml/#charsets>.
+fn is_allowed(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
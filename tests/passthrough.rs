@@ -0,0 +1,93 @@
+//! Tests of the passthrough fast path
+//!
+//! These tests compare the output of escapers reporting passthrough characters
+//! against the output of the same escapers wrapped such that they never report
+//! passthrough.
+
+use std::fmt;
+
+use rescue_blanket::{Chain, Escapable, Escaper, TableEscaper};
+use rescue_blanket::{c, csv, html, json, rust, shell, sql, url, xml};
+
+
+const INPUTS: [&str; 4] = [
+    "plain <a/ </script></a>\u{2028}<\u{2029}/ \u{1}Ab\u{1}g ??=???",
+    "\"q\" it's x,y\ta\r\n\u{202e} é&<>\0 %/#+ [a]] `b` C:\\dir",
+    "say \"#hi\"## ",
+    "",
+];
+
+static TABLE: TableEscaper = TableEscaper::new(&[('&', "\\&"), ('%', "\\%")]);
+
+
+#[test]
+fn markup() {
+    check(html::Text);
+    check(html::Attribute);
+    check(xml::Text::default());
+    check(xml::Attribute::default());
+}
+
+#[test]
+fn literals() {
+    check(json::StringContent::new());
+    check(json::StringContent::new().script_safe(true).ascii_only(true));
+    check(c::StringContent::new());
+    check(c::StringContent::new().numeric(c::Numeric::Hex));
+    check(rust::StringContent);
+    check(rust::Raw::new());
+    check(sql::StringLiteral::new(sql::Dialect::MySql));
+    check(sql::Identifier::new(sql::Dialect::SqlServer));
+}
+
+#[test]
+fn misc() {
+    check(shell::Quote);
+    check(&TABLE);
+    check(url::Encode::default());
+    check(url::Encode::new(url::Set::FORM));
+    check(csv::Field::new());
+    check(csv::Field::tsv().always_quote(true));
+    check(Chain::new(json::StringContent::new(), shell::Quote));
+}
+
+
+/// Check that an escaper produces the same output with and without passthrough
+fn check<E: Escaper>(escaper: E) {
+    for s in INPUTS {
+        assert_eq!(
+            s.escaped_with(escaper.clone()).to_string(),
+            s.escaped_with(NoPassthrough(escaper.clone())).to_string(),
+            "input: {s:?}",
+        );
+    }
+}
+
+
+/// [Escaper] wrapper which never reports passthrough
+#[derive(Clone)]
+struct NoPassthrough<E>(E);
+
+impl<E: Escaper> Escaper for NoPassthrough<E> {
+    type Output = E::Output;
+
+    fn process(&mut self, input: char) -> Self::Output {
+        self.0.process(input)
+    }
+
+    fn start_lookahead(&mut self) -> bool {
+        self.0.start_lookahead()
+    }
+
+    fn inspect(&mut self, input: char) {
+        self.0.inspect(input)
+    }
+
+    fn start(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.0.start(out)
+    }
+
+    fn finish(&mut self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.0.finish(out)
+    }
+}